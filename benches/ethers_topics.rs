@@ -0,0 +1,47 @@
+//! Measures converting the topics of ethers logs, and decoding them end to
+//! end. Run with `cargo bench --bench ethers_topics`.
+
+use alloy_dyn_parser::{EventLog, Parser};
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::Log;
+use ethers::core::types::{Bytes, Log as EthersLog, H160, H256};
+use std::hint::black_box;
+use std::time::Instant;
+
+const ROUNDS: usize = 10_000;
+
+fn measure(label: &str, logs: &[EthersLog], mut run: impl FnMut(&EthersLog)) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        logs.iter().for_each(&mut run);
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{label:>8}: {:>8.2?}/log",
+        elapsed / (ROUNDS * logs.len()) as u32
+    );
+}
+
+fn main() {
+    let abi: JsonAbi = serde_json::from_str(include_str!("../testdata/erc20.json")).unwrap();
+    let response: serde_json::Value =
+        serde_json::from_str(include_str!("../testdata/logs.json")).unwrap();
+    let logs: Vec<Log> = serde_json::from_value(response["result"].clone()).unwrap();
+    let logs: Vec<EthersLog> = logs
+        .iter()
+        .map(|log| EthersLog {
+            address: H160(log.address.into_array()),
+            topics: log.topics().iter().map(|t| H256(t.0)).collect(),
+            data: Bytes::from(log.data.data.to_vec()),
+            ..Default::default()
+        })
+        .collect();
+    let parser = Parser::new(&abi);
+
+    measure("topics", &logs, |log| {
+        black_box(EventLog::topics(log));
+    });
+    measure("parse", &logs, |log| {
+        black_box(parser.parse(log).unwrap());
+    });
+}
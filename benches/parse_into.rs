@@ -0,0 +1,63 @@
+//! Compares allocations and wall time of `Parser::parse` against reusing one
+//! buffer with `Parser::parse_into`. Run with `cargo bench --bench parse_into`.
+
+use alloy_dyn_parser::{KeyedEvent, Parser};
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::Log;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const ROUNDS: usize = 1_000;
+
+fn measure(label: &str, logs: &[Log], mut decode: impl FnMut(&Log)) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        logs.iter().for_each(&mut decode);
+    }
+    let elapsed = start.elapsed();
+    let decoded = (ROUNDS * logs.len()) as f64;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) as f64;
+    println!(
+        "{label:>10}: {:>8.1} allocations/log {:>8.2?}/log",
+        allocations / decoded,
+        elapsed / (ROUNDS * logs.len()) as u32,
+    );
+}
+
+fn main() {
+    let abi: JsonAbi = serde_json::from_str(include_str!("../testdata/erc20.json")).unwrap();
+    let response: serde_json::Value =
+        serde_json::from_str(include_str!("../testdata/logs.json")).unwrap();
+    let logs: Vec<Log> = serde_json::from_value(response["result"].clone()).unwrap();
+    let parser = Parser::new(&abi);
+
+    measure("parse", &logs, |log| {
+        black_box(parser.parse(log).unwrap());
+    });
+    let mut out = KeyedEvent::default();
+    measure("parse_into", &logs, |log| {
+        parser.parse_into(log, &mut out).unwrap();
+        black_box(&out);
+    });
+}
@@ -0,0 +1,173 @@
+use crate::{
+    AddressCase, BytesEncoding, IntEncoding, KeyCase, OutputOptions, Parser, UnnamedParameters,
+};
+use alloc::string::String;
+use alloy_json_abi::JsonAbi;
+
+/// Configures a [`Parser`] one option at a time. [`Parser::new`] remains the
+/// shortcut for the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParserBuilder {
+    options: OutputOptions,
+}
+
+impl ParserBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from an existing set of options.
+    pub fn from_options(options: OutputOptions) -> Self {
+        Self { options }
+    }
+
+    /// See [`OutputOptions::int_encoding`].
+    pub fn int_encoding(mut self, encoding: IntEncoding) -> Self {
+        self.options.int_encoding = encoding;
+        self
+    }
+
+    /// See [`OutputOptions::bytes_encoding`].
+    pub fn bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.options.bytes_encoding = encoding;
+        self
+    }
+
+    /// See [`OutputOptions::fixed_bytes_encoding`].
+    pub fn fixed_bytes_encoding(mut self, encoding: BytesEncoding) -> Self {
+        self.options.fixed_bytes_encoding = Some(encoding);
+        self
+    }
+
+    /// See [`OutputOptions::numbers_as_json_when_small`].
+    pub fn numbers_as_json_when_small(mut self, enabled: bool) -> Self {
+        self.options.numbers_as_json_when_small = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::split_indexed`].
+    pub fn split_indexed(mut self, enabled: bool) -> Self {
+        self.options.split_indexed = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::include_signature`].
+    pub fn include_signature(mut self, enabled: bool) -> Self {
+        self.options.include_signature = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::address_case`].
+    pub fn address_case(mut self, case: AddressCase) -> Self {
+        self.options.address_case = case;
+        self
+    }
+
+    /// See [`OutputOptions::include_struct_type`].
+    pub fn include_struct_type(mut self, enabled: bool) -> Self {
+        self.options.include_struct_type = enabled;
+        self
+    }
+
+    /// Flattens the decoded fields, joining keys with `separator`. See
+    /// [`OutputOptions::flatten`].
+    pub fn flatten(mut self, separator: char) -> Self {
+        self.options.flatten = true;
+        self.options.separator = separator;
+        self
+    }
+
+    /// See [`OutputOptions::lenient_data`].
+    pub fn lenient_data(mut self, enabled: bool) -> Self {
+        self.options.lenient_data = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::strict`].
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.options.strict = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::sorted_keys`].
+    pub fn sorted_keys(mut self, enabled: bool) -> Self {
+        self.options.sorted_keys = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::unnamed_parameters`].
+    pub fn unnamed_parameters(mut self, handling: UnnamedParameters) -> Self {
+        self.options.unnamed_parameters = handling;
+        self
+    }
+
+    /// See [`OutputOptions::include_types`].
+    pub fn include_types(mut self, enabled: bool) -> Self {
+        self.options.include_types = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::passthrough_unknown`].
+    pub fn passthrough_unknown(mut self, enabled: bool) -> Self {
+        self.options.passthrough_unknown = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::address_prefix`].
+    pub fn address_prefix(mut self, enabled: bool) -> Self {
+        self.options.address_prefix = enabled;
+        self
+    }
+
+    /// Scales the `uintN` parameter `field` by `decimals`. See
+    /// [`OutputOptions::decimals`].
+    pub fn decimals(mut self, field: impl Into<String>, decimals: u8) -> Self {
+        self.options.decimals.insert(field.into(), decimals);
+        self
+    }
+
+    /// See [`OutputOptions::fixed_bytes_as_array`].
+    pub fn fixed_bytes_as_array(mut self, enabled: bool) -> Self {
+        self.options.fixed_bytes_as_array = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::key_case`].
+    pub fn key_case(mut self, case: KeyCase) -> Self {
+        self.options.key_case = case;
+        self
+    }
+
+    /// See [`OutputOptions::include_raw_topics`].
+    pub fn include_raw_topics(mut self, enabled: bool) -> Self {
+        self.options.include_raw_topics = enabled;
+        self
+    }
+
+    /// See [`OutputOptions::max_depth`].
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.options.max_depth = depth;
+        self
+    }
+
+    /// See [`OutputOptions::etherscan_style`].
+    pub fn etherscan_style(mut self, enabled: bool) -> Self {
+        self.options.etherscan_style = enabled;
+        self
+    }
+
+    /// The options configured so far.
+    pub fn options(&self) -> &OutputOptions {
+        &self.options
+    }
+
+    /// Builds a parser for a single ABI.
+    pub fn build(self, abi: &JsonAbi) -> Parser<'_> {
+        Parser::with_options(abi, self.options)
+    }
+
+    /// Builds a parser for several ABIs. See [`Parser::from_abis`].
+    pub fn build_from_abis(self, abis: &[JsonAbi]) -> Parser<'_> {
+        Parser::from_abis_with_options(abis, self.options)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{json_to_dyn_sol, KeyedEvent};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{Address, U256};
+use serde_json::Value;
+
+/// An ERC20 `Transfer(address indexed from, address indexed to, uint256 value)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+/// An ERC20 `Approval(address indexed owner, address indexed spender, uint256 value)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Approval {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+}
+
+impl KeyedEvent {
+    /// Reads the event back as an ERC20 `Transfer`. `None` for other events,
+    /// including ERC721 transfers, which have no `value`. The `value` must
+    /// not have been scaled by [`OutputOptions::decimals`], which the event
+    /// doesn't record.
+    ///
+    /// [`OutputOptions::decimals`]: crate::OutputOptions::decimals
+    pub fn as_transfer(&self) -> Option<Transfer> {
+        if self.name != "Transfer" {
+            return None;
+        }
+        Some(Transfer {
+            from: self.address("from")?,
+            to: self.address("to")?,
+            value: self.uint("value")?,
+        })
+    }
+
+    /// Reads the event back as an ERC20 `Approval`, like
+    /// [`KeyedEvent::as_transfer`].
+    pub fn as_approval(&self) -> Option<Approval> {
+        if self.name != "Approval" {
+            return None;
+        }
+        Some(Approval {
+            owner: self.address("owner")?,
+            spender: self.address("spender")?,
+            value: self.uint("value")?,
+        })
+    }
+
+    /// A top-level field, also with [`OutputOptions::split_indexed`].
+    ///
+    /// [`OutputOptions::split_indexed`]: crate::OutputOptions::split_indexed
+    fn field(&self, name: &str) -> Option<&Value> {
+        self.data
+            .get(name)
+            .or_else(|| self.data.get("indexed")?.get(name))
+            .or_else(|| self.data.get("body")?.get(name))
+    }
+
+    fn address(&self, name: &str) -> Option<Address> {
+        match json_to_dyn_sol(self.field(name)?, &DynSolType::Address).ok()? {
+            DynSolValue::Address(address) => Some(address),
+            _ => None,
+        }
+    }
+
+    fn uint(&self, name: &str) -> Option<U256> {
+        match json_to_dyn_sol(self.field(name)?, &DynSolType::Uint(256)).ok()? {
+            DynSolValue::Uint(value, _) => Some(value),
+            _ => None,
+        }
+    }
+}
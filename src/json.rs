@@ -0,0 +1,1591 @@
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::Param;
+use alloy_primitives::{Address, Function, B256, I256, U256};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::ParsingError;
+
+/// Key under which [`OutputOptions::include_struct_type`] stores the struct name.
+const STRUCT_TYPE_KEY: &str = "__type";
+
+/// Controls how decoded values are represented as JSON.
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    /// Encoding used for `intN` and `uintN` values which aren't emitted as
+    /// JSON numbers.
+    pub int_encoding: IntEncoding,
+    /// Encoding used for `bytes` and, unless `fixed_bytes_encoding` is set,
+    /// `bytesN` values.
+    pub bytes_encoding: BytesEncoding,
+    /// Encoding used for `bytesN` values only, overriding `bytes_encoding`.
+    /// Fixed-size values such as `bytes32` role IDs and hashes are usually
+    /// displayed as hex. Defaults to `None`, following `bytes_encoding`.
+    pub fixed_bytes_encoding: Option<BytesEncoding>,
+    /// Emit `uintN` values which fit in a `u64`, and `intN` values which fit
+    /// in an `i64`, as JSON numbers. Larger values are still emitted as
+    /// strings, following `int_encoding`, so the representation of a field
+    /// may vary between events. Defaults to `false`, emitting every integer
+    /// as a string.
+    ///
+    /// With the `arbitrary-precision` feature and [`IntEncoding::Decimal`]
+    /// every integer is a JSON number regardless of this flag;
+    /// [`IntEncoding::Hex`] still emits strings.
+    pub numbers_as_json_when_small: bool,
+    /// Produce `{ "indexed": {...}, "body": {...} }` for decoded events
+    /// instead of a single flat object. Indexed dynamic values (`string`,
+    /// `bytes`, arrays and structs) are keccak256 hashes of the original
+    /// value, emitted as `{"hashed": ...}`, or `{"structHash": ...}` for
+    /// structs, which this makes visible to consumers.
+    pub split_indexed: bool,
+    /// Add the canonical signature, e.g. `Transfer(address,address,uint256)`,
+    /// to every decoded event.
+    pub include_signature: bool,
+    /// Casing of `address` values.
+    pub address_case: AddressCase,
+    /// Prefix `address` values with `0x`. Defaults to `true`; disable to emit
+    /// bare hex, e.g. for column stores. Either form is accepted when
+    /// converting back from JSON.
+    pub address_prefix: bool,
+    /// Add a `"__type": "<StructName>"` key to every object decoded from a
+    /// Solidity struct. A struct member which is itself called `__type` takes
+    /// precedence over the type name.
+    pub include_struct_type: bool,
+    /// Turn the decoded fields into a single-level object, joining the keys
+    /// of nested objects and the indices of arrays with `separator`, e.g.
+    /// `packet.timeout.0`. Empty objects and arrays are kept as values.
+    /// [`Parser::encode`] rebuilds the nesting from the ABI, so member names
+    /// may contain `separator`.
+    ///
+    /// [`Parser::encode`]: crate::Parser::encode
+    pub flatten: bool,
+    /// Separator used by [`OutputOptions::flatten`]. Defaults to `.`.
+    pub separator: char,
+    /// Retry logs whose data fails to decode and isn't a multiple of 32
+    /// bytes with the data zero-padded to the next word boundary. Some
+    /// archival providers strip trailing zero bytes. Decoded events record
+    /// that padding was applied. Zero topics past the ones the event expects,
+    /// which some indexers pad logs with, are dropped as well.
+    pub lenient_data: bool,
+    /// Validate logs against the event definition, e.g. that topic0 is the
+    /// event's selector when decoding with [`Parser::parse_as`]. When
+    /// disabled, logs which fail validation are decoded again without it, and
+    /// the event records that it was decoded non-strictly. Topics beyond the
+    /// event's indexed inputs are then set aside as `extra_topics` rather
+    /// than rejected. Strictness also rejects `bytesN` values with non-zero
+    /// bytes past `N`, both in JSON converted back into values and in values
+    /// passed to [`try_dyn_sol_to_json_with`]. Defaults to `true`.
+    ///
+    /// [`Parser::parse_as`]: crate::Parser::parse_as
+    pub strict: bool,
+    /// Order the keys of every object lexicographically, at any depth, so
+    /// that serializing the same event always yields the same bytes, e.g. for
+    /// content addressing. `serde_json` only sorts keys on its own when the
+    /// `preserve_order` feature is disabled throughout the build.
+    pub sorted_keys: bool,
+    /// How to key parameters without a name in the ABI. By default they are
+    /// keyed by their position, so that several unnamed parameters don't
+    /// collide on the empty key.
+    pub unnamed_parameters: UnnamedParameters,
+    /// Add a `types` map to decoded events, keyed like the top-level fields,
+    /// with the canonical Solidity type of each parameter from the ABI, e.g.
+    /// `uint256`, `address[]` or `(uint256,bytes)`.
+    pub include_types: bool,
+    /// Turn logs with an unknown or missing topic0 into an `UnknownEvent`
+    /// holding the raw `topic0`, `topics` and `data` as hex, instead of
+    /// failing with [`ParsingError::UnknownEvent`], so that every log can be
+    /// stored in the same shape. Applies to [`Parser::parse`] and the methods
+    /// built on it; [`Parser::parse_opt`] then never returns `None`.
+    ///
+    /// [`ParsingError::UnknownEvent`]: crate::ParsingError::UnknownEvent
+    /// [`Parser::parse`]: crate::Parser::parse
+    /// [`Parser::parse_opt`]: crate::Parser::parse_opt
+    pub passthrough_unknown: bool,
+    /// Scale the `uintN` parameters with these names by `10^decimals`,
+    /// emitting decimal strings such as `"1.5"` for a token amount of
+    /// `1500000000000000000` with 18 decimals. Token decimals aren't part of
+    /// the ABI, so they have to be supplied per field. Only top-level
+    /// parameters are matched. [`Parser::encode`] multiplies such fields back
+    /// by `10^decimals`, failing with [`ConversionError::OutOfRange`] on
+    /// amounts with more fractional digits than `decimals` or which don't
+    /// fit in the parameter's type.
+    ///
+    /// [`Parser::encode`]: crate::Parser::encode
+    pub decimals: BTreeMap<String, u8>,
+    /// Emit `bytes` and `bytesN` values as JSON arrays of numbers, e.g.
+    /// `[222, 173, 190, 239]`, taking precedence over `bytes_encoding` and
+    /// `fixed_bytes_encoding`. A `bytesN` array holds only its `N` bytes.
+    /// Arrays are accepted when converting back from JSON regardless.
+    pub fixed_bytes_as_array: bool,
+    /// Naming convention applied to the keys of decoded fields, including
+    /// struct and tuple members, e.g. `packetSequence` for `packet_sequence`
+    /// with [`KeyCase::CamelCase`]. Keys are converted back when turning
+    /// JSON into values. [`OutputOptions::decimals`] still matches the names
+    /// in the ABI.
+    pub key_case: KeyCase,
+    /// Add the log's topics, including topic0, and data as `raw_topics` and
+    /// `raw_data` to decoded events, so that the decoding can be verified
+    /// downstream. Both are `0x`-hex, and kept outside of `data` regardless
+    /// of [`OutputOptions::split_indexed`].
+    pub include_raw_topics: bool,
+    /// The deepest nesting of arrays, tuples and structs in a decoded value
+    /// which is converted to JSON. Conversion recurses once per level, so
+    /// [`Parser`] methods and [`try_dyn_sol_to_json_with`] fail with
+    /// [`ParsingError::DepthExceeded`] beyond it instead of risking a stack
+    /// overflow on pathological ABIs. Defaults to 128.
+    ///
+    /// [`Parser`]: crate::Parser
+    /// [`ParsingError::DepthExceeded`]: crate::ParsingError::DepthExceeded
+    pub max_depth: usize,
+    /// Emit the data of decoded events like Etherscan's decoded logs, as
+    /// `{"params": [{"name", "type", "value", "indexed"}, ...]}` in ABI
+    /// order, which many frontends recognize. Takes precedence over
+    /// `split_indexed` and `flatten`. Such events can't be passed to
+    /// [`Parser::encode`].
+    ///
+    /// [`Parser::encode`]: crate::Parser::encode
+    pub etherscan_style: bool,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            int_encoding: IntEncoding::default(),
+            bytes_encoding: BytesEncoding::default(),
+            fixed_bytes_encoding: None,
+            numbers_as_json_when_small: false,
+            split_indexed: false,
+            include_signature: false,
+            address_case: AddressCase::default(),
+            address_prefix: true,
+            include_struct_type: false,
+            flatten: false,
+            separator: '.',
+            lenient_data: false,
+            strict: true,
+            sorted_keys: false,
+            unnamed_parameters: UnnamedParameters::default(),
+            include_types: false,
+            passthrough_unknown: false,
+            decimals: BTreeMap::new(),
+            fixed_bytes_as_array: false,
+            key_case: KeyCase::default(),
+            include_raw_topics: false,
+            max_depth: 128,
+            etherscan_style: false,
+        }
+    }
+}
+
+impl OutputOptions {
+    fn fixed_encoding(&self) -> BytesEncoding {
+        self.fixed_bytes_encoding.unwrap_or(self.bytes_encoding)
+    }
+}
+
+/// Handling of ABI parameters with an empty name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnnamedParameters {
+    /// Key the parameter as `arg<N>`, where `N` is its position among the
+    /// ABI inputs, e.g. `arg0`.
+    #[default]
+    Positional,
+    /// Fail with [`ParsingError::UnnamedParameter`].
+    ///
+    /// [`ParsingError::UnnamedParameter`]: crate::ParsingError::UnnamedParameter
+    Reject,
+}
+
+/// The key of the parameter at `index` called `name`, which is only
+/// positional for unnamed parameters.
+pub(crate) fn param_key(name: &str, index: usize, case: KeyCase) -> String {
+    if name.is_empty() {
+        format!("arg{index}")
+    } else {
+        case.convert(name)
+    }
+}
+
+/// Naming convention of the keys of decoded fields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyCase {
+    /// Keep the names from the ABI.
+    #[default]
+    AsIs,
+    /// `packetSequence`, `tokenId`.
+    CamelCase,
+    /// `packet_sequence`, `token_id`.
+    SnakeCase,
+}
+
+impl KeyCase {
+    /// Converts `name` to this convention. Words are split at underscores
+    /// and changes of case, keeping runs of capitals together as acronyms,
+    /// so `tokenID` and `IBCPacket` become `token_id` and `ibc_packet` in
+    /// snake case. Leading and trailing underscores are kept as they are,
+    /// since they often carry meaning, e.g. `_value`.
+    pub fn convert(self, name: &str) -> String {
+        let core = name.trim_matches('_');
+        if self == KeyCase::AsIs || core.is_empty() {
+            return name.to_owned();
+        }
+        let leading = name.len() - name.trim_start_matches('_').len();
+        let mut key = String::with_capacity(name.len() + 4);
+        key.push_str(&name[..leading]);
+        for (i, word) in words(core).into_iter().enumerate() {
+            let word = word.to_lowercase();
+            match self {
+                KeyCase::SnakeCase if i > 0 => key.push('_'),
+                KeyCase::CamelCase if i > 0 => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        key.extend(first.to_uppercase());
+                        key.push_str(chars.as_str());
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            key.push_str(&word);
+        }
+        key.push_str(&name[leading + core.len()..]);
+        key
+    }
+}
+
+/// Splits an identifier into words at underscores, at a capital following a
+/// lowercase letter or digit, and before the last capital of a run followed
+/// by a lowercase letter, e.g. `IBC|Packet`.
+fn words(name: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = name.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = None;
+    for (n, &(i, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if let Some(start) = start.take() {
+                words.push(&name[start..i]);
+            }
+            continue;
+        }
+        let Some(word_start) = start else {
+            start = Some(i);
+            continue;
+        };
+        let prev = chars[n - 1].1;
+        let next = chars.get(n + 1).map(|&(_, c)| c);
+        let boundary = c.is_uppercase()
+            && (prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next.is_some_and(char::is_lowercase)));
+        if boundary {
+            words.push(&name[word_start..i]);
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        words.push(&name[start..]);
+    }
+    words
+}
+
+/// Encoding used for integer values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IntEncoding {
+    /// Decimal digits, with a leading `-` for negative values.
+    #[default]
+    Decimal,
+    /// Lowercase hex prefixed with `0x`, like JSON-RPC quantities, e.g.
+    /// `0x1f4`. Negative values are the two's complement at the bit width of
+    /// their type, so an `int8` of -1 is `0xff`.
+    Hex,
+}
+
+/// Encoding used for byte values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BytesEncoding {
+    /// Standard base64 with padding.
+    #[default]
+    Base64,
+    /// Lowercase hex, prefixed with `0x`.
+    Hex,
+}
+
+impl BytesEncoding {
+    fn encode(self, bytes: &[u8]) -> String {
+        use base64::prelude::*;
+
+        match self {
+            BytesEncoding::Base64 => BASE64_STANDARD.encode(bytes),
+            BytesEncoding::Hex => alloy_primitives::hex::encode_prefixed(bytes),
+        }
+    }
+
+    fn decode(self, encoded: &str) -> Option<Vec<u8>> {
+        use base64::prelude::*;
+
+        match self {
+            BytesEncoding::Base64 => BASE64_STANDARD.decode(encoded).ok(),
+            BytesEncoding::Hex => alloy_primitives::hex::decode(encoded).ok(),
+        }
+    }
+}
+
+/// Casing of address values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressCase {
+    /// Mixed-case EIP-55 checksum encoding.
+    #[default]
+    Checksummed,
+    /// All lowercase, for case-insensitive joins.
+    Lowercase,
+}
+
+impl AddressCase {
+    fn encode(self, address: Address, prefix: bool) -> String {
+        let encoded = match self {
+            AddressCase::Checksummed => address.to_checksum(None),
+            AddressCase::Lowercase => alloy_primitives::hex::encode_prefixed(address),
+        };
+        match prefix {
+            true => encoded,
+            false => encoded[2..].to_string(),
+        }
+    }
+}
+
+/// Converts a decoded value to JSON using the default [`OutputOptions`].
+///
+/// Recurses once per level of nesting without checking
+/// [`OutputOptions::max_depth`]; see [`try_dyn_sol_to_json_with`] for values
+/// decoded with untrusted ABIs.
+pub fn dyn_sol_to_json(val: DynSolValue) -> Value {
+    dyn_sol_to_json_with(val, &OutputOptions::default())
+}
+
+/// Converts a decoded value to JSON according to `opts`, without checking
+/// [`OutputOptions::max_depth`]; see [`try_dyn_sol_to_json_with`].
+pub fn dyn_sol_to_json_with(val: DynSolValue, opts: &OutputOptions) -> Value {
+    dyn_sol_to_json_with_formatter(val, opts, |_| None)
+}
+
+/// Like [`dyn_sol_to_json_with`], but fails with
+/// [`ParsingError::DepthExceeded`] instead of converting a value nested
+/// deeper than [`OutputOptions::max_depth`]. With [`OutputOptions::strict`],
+/// a `bytesN` value with non-zero bytes past `N`, which only hand-built
+/// values can carry, fails with [`ConversionError::LengthMismatch`].
+///
+/// [`ParsingError::DepthExceeded`]: crate::ParsingError::DepthExceeded
+pub fn try_dyn_sol_to_json_with(
+    val: DynSolValue,
+    opts: &OutputOptions,
+) -> Result<Value, ParsingError> {
+    try_dyn_sol_to_json_with_formatter(val, opts, |_| None)
+}
+
+/// Like [`dyn_sol_to_json_with_formatter`], but checks the value like
+/// [`try_dyn_sol_to_json_with`].
+pub fn try_dyn_sol_to_json_with_formatter(
+    val: DynSolValue,
+    opts: &OutputOptions,
+    formatter: impl Fn(&DynSolValue) -> Option<Value>,
+) -> Result<Value, ParsingError> {
+    check_depth([&val], opts.max_depth)?;
+    if opts.strict {
+        let mut stack = vec![&val];
+        while let Some(value) = stack.pop() {
+            match value {
+                DynSolValue::FixedBytes(word, size) => check_fixed_bytes(word, *size)?,
+                DynSolValue::Array(items)
+                | DynSolValue::FixedArray(items)
+                | DynSolValue::Tuple(items)
+                | DynSolValue::CustomStruct { tuple: items, .. } => stack.extend(items),
+                _ => {}
+            }
+        }
+    }
+    Ok(dyn_sol_to_json_with_formatter(val, opts, formatter))
+}
+
+/// Fails if a `bytes{size}` word has non-zero bytes past `size`, which JSON
+/// would carry but re-encoding would silently drop.
+fn check_fixed_bytes(word: &B256, size: usize) -> Result<(), ConversionError> {
+    let used = word.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    if used > size || size > 32 {
+        return Err(ConversionError::LengthMismatch {
+            ty: format!("bytes{size}"),
+            expected: size,
+            found: used.max(size.min(32)),
+        });
+    }
+    Ok(())
+}
+
+/// Like [`dyn_sol_to_json_with`], but first offers every value, at any depth,
+/// to `formatter`. Where it returns `Some`, that JSON is used in place of the
+/// built-in conversion of the value and anything nested in it; where it
+/// returns `None`, the value is converted according to `opts`. Doesn't check
+/// [`OutputOptions::max_depth`]; see [`try_dyn_sol_to_json_with_formatter`].
+pub fn dyn_sol_to_json_with_formatter(
+    val: DynSolValue,
+    opts: &OutputOptions,
+    formatter: impl Fn(&DynSolValue) -> Option<Value>,
+) -> Value {
+    let json = format_json(val, opts, &formatter);
+    if opts.sorted_keys {
+        sort_keys(json)
+    } else {
+        json
+    }
+}
+
+fn to_json(val: DynSolValue, opts: &OutputOptions) -> Value {
+    format_json(val, opts, &|_| None)
+}
+
+fn format_json<F>(val: DynSolValue, opts: &OutputOptions, formatter: &F) -> Value
+where
+    F: Fn(&DynSolValue) -> Option<Value>,
+{
+    if let Some(json) = formatter(&val) {
+        return json;
+    }
+    let convert = |a: Vec<DynSolValue>| -> Vec<Value> {
+        a.into_iter()
+            .map(|v| format_json(v, opts, formatter))
+            .collect()
+    };
+
+    match val {
+        DynSolValue::Bool(b) => Value::Bool(b),
+        DynSolValue::Int(i, bits) => match i64::try_from(i) {
+            Ok(small) if opts.numbers_as_json_when_small => Value::from(small),
+            _ => match opts.int_encoding {
+                IntEncoding::Decimal => decimal(i.to_dec_string()),
+                IntEncoding::Hex => Value::String(quantity(twos_complement(i, bits))),
+            },
+        },
+        DynSolValue::Uint(i, _) => match u64::try_from(i) {
+            Ok(small) if opts.numbers_as_json_when_small => Value::from(small),
+            _ => match opts.int_encoding {
+                IntEncoding::Decimal => decimal(i.to_string()),
+                IntEncoding::Hex => Value::String(quantity(i)),
+            },
+        },
+        DynSolValue::FixedBytes(v, size) if opts.fixed_bytes_as_array => {
+            byte_array(&v[..size.min(32)])
+        }
+        DynSolValue::FixedBytes(v, _) => Value::String(opts.fixed_encoding().encode(&v.0)),
+        DynSolValue::Address(a) => Value::String(opts.address_case.encode(a, opts.address_prefix)),
+        DynSolValue::Function(p) => Value::String(alloy_primitives::hex::encode_prefixed(p)),
+        DynSolValue::Bytes(b) if opts.fixed_bytes_as_array => byte_array(&b),
+        DynSolValue::Bytes(b) => Value::String(opts.bytes_encoding.encode(&b)),
+        DynSolValue::String(s) => Value::String(s),
+        DynSolValue::Array(a) => Value::Array(convert(a)),
+        DynSolValue::FixedArray(a) => Value::Array(convert(a)),
+        DynSolValue::Tuple(a) => Value::Array(convert(a)),
+        DynSolValue::CustomStruct {
+            name,
+            prop_names,
+            tuple,
+        } => struct_to_json(name, prop_names, convert(tuple), opts),
+    }
+}
+
+/// Bytes as a JSON array of numbers, for [`OutputOptions::fixed_bytes_as_array`].
+fn byte_array(bytes: &[u8]) -> Value {
+    Value::Array(bytes.iter().map(|&b| Value::from(b)).collect())
+}
+
+/// Inverse of [`byte_array`].
+fn bytes_from_array(value: &Value) -> Option<Vec<u8>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+        .collect()
+}
+
+/// Errors returned when converting JSON back into a [`DynSolValue`].
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    /// The JSON value has the wrong shape for the Solidity type, such as a
+    /// number where an address is expected, or an undecodable string.
+    #[error("cannot convert {found} to {expected}")]
+    TypeMismatch { expected: String, found: Value },
+    /// An array, tuple or `bytesN` value has the wrong number of elements.
+    #[error("expected {expected} elements for {ty}, found {found}")]
+    LengthMismatch {
+        ty: String,
+        expected: usize,
+        found: usize,
+    },
+    /// An integer does not fit in the number of bits of its type.
+    #[error("{value} is out of range for {ty}")]
+    OutOfRange { ty: String, value: String },
+    /// A struct member is missing from the JSON object.
+    #[error("missing field `{name}` of struct {ty}")]
+    MissingField { ty: String, name: String },
+}
+
+/// Converts JSON produced by [`dyn_sol_to_json`] back into a value of type
+/// `ty`, using the default [`OutputOptions`].
+pub fn json_to_dyn_sol(value: &Value, ty: &DynSolType) -> Result<DynSolValue, ConversionError> {
+    json_to_dyn_sol_with(value, ty, &OutputOptions::default())
+}
+
+/// Converts JSON produced by [`dyn_sol_to_json_with`] back into a value of
+/// type `ty`. Bytes are decoded according to `opts`; integers may be decimal
+/// strings or JSON numbers, addresses may be in any case, and structs may be
+/// objects keyed by member name or positional arrays.
+pub fn json_to_dyn_sol_with(
+    value: &Value,
+    ty: &DynSolType,
+    opts: &OutputOptions,
+) -> Result<DynSolValue, ConversionError> {
+    let mismatch = || ConversionError::TypeMismatch {
+        expected: ty.to_string(),
+        found: value.clone(),
+    };
+    let sequence = |types: &[DynSolType]| -> Result<Vec<DynSolValue>, ConversionError> {
+        let items = value.as_array().ok_or_else(mismatch)?;
+        if items.len() != types.len() {
+            return Err(ConversionError::LengthMismatch {
+                ty: ty.to_string(),
+                expected: types.len(),
+                found: items.len(),
+            });
+        }
+        items
+            .iter()
+            .zip(types)
+            .map(|(v, t)| json_to_dyn_sol_with(v, t, opts))
+            .collect()
+    };
+    let digits = || match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+    let out_of_range = |digits: String| ConversionError::OutOfRange {
+        ty: ty.to_string(),
+        value: digits,
+    };
+
+    match ty {
+        DynSolType::Bool => value.as_bool().map(DynSolValue::Bool).ok_or_else(mismatch),
+        DynSolType::Int(bits) => {
+            let digits = digits().ok_or_else(mismatch)?;
+            let int = match digits.strip_prefix("0x") {
+                Some(hex) => {
+                    let raw = U256::from_str_radix(hex, 16).map_err(|_| mismatch())?;
+                    if raw.bit_len() > *bits {
+                        return Err(out_of_range(digits));
+                    }
+                    sign_extend(raw, *bits)
+                }
+                None => I256::from_dec_str(&digits).map_err(|_| mismatch())?,
+            };
+            if int.bits() as usize > *bits {
+                return Err(out_of_range(digits));
+            }
+            Ok(DynSolValue::Int(int, *bits))
+        }
+        DynSolType::Uint(bits) => {
+            let digits = digits().ok_or_else(mismatch)?;
+            let uint = match digits.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16),
+                None => U256::from_str_radix(&digits, 10),
+            }
+            .map_err(|_| mismatch())?;
+            if uint.bit_len() > *bits {
+                return Err(out_of_range(digits));
+            }
+            Ok(DynSolValue::Uint(uint, *bits))
+        }
+        DynSolType::FixedBytes(size) => {
+            let bytes = value
+                .as_str()
+                .and_then(|s| opts.fixed_encoding().decode(s))
+                .or_else(|| bytes_from_array(value))
+                .ok_or_else(mismatch)?;
+            // Accept both the full word `dyn_sol_to_json` emits and exactly
+            // `size` bytes.
+            let length_mismatch = |found| ConversionError::LengthMismatch {
+                ty: ty.to_string(),
+                expected: *size,
+                found,
+            };
+            let word = match bytes.len() {
+                32 => B256::from_slice(&bytes),
+                len if len == *size && len < 32 => B256::right_padding_from(&bytes),
+                len => return Err(length_mismatch(len)),
+            };
+            if opts.strict {
+                check_fixed_bytes(&word, *size)?;
+            }
+            Ok(DynSolValue::FixedBytes(word, *size))
+        }
+        DynSolType::Address => value
+            .as_str()
+            .and_then(|s| s.parse::<Address>().ok())
+            .map(DynSolValue::Address)
+            .ok_or_else(mismatch),
+        DynSolType::Function => value
+            .as_str()
+            .and_then(|s| s.parse::<Function>().ok())
+            .map(DynSolValue::Function)
+            .ok_or_else(mismatch),
+        DynSolType::Bytes => value
+            .as_str()
+            .and_then(|s| opts.bytes_encoding.decode(s))
+            .or_else(|| bytes_from_array(value))
+            .map(DynSolValue::Bytes)
+            .ok_or_else(mismatch),
+        DynSolType::String => value
+            .as_str()
+            .map(|s| DynSolValue::String(s.to_owned()))
+            .ok_or_else(mismatch),
+        DynSolType::Array(inner) => value
+            .as_array()
+            .ok_or_else(mismatch)?
+            .iter()
+            .map(|v| json_to_dyn_sol_with(v, inner, opts))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::Array),
+        DynSolType::FixedArray(inner, len) => {
+            sequence(&vec![(**inner).clone(); *len]).map(DynSolValue::FixedArray)
+        }
+        DynSolType::Tuple(types) => sequence(types).map(DynSolValue::Tuple),
+        DynSolType::CustomStruct {
+            name,
+            prop_names,
+            tuple,
+        } => {
+            let values = match value {
+                Value::Object(map) => prop_names
+                    .iter()
+                    .zip(tuple)
+                    .map(|(prop, t)| {
+                        let key = opts.key_case.convert(prop);
+                        let v = map.get(&key).ok_or_else(|| ConversionError::MissingField {
+                            ty: name.clone(),
+                            name: key.clone(),
+                        })?;
+                        json_to_dyn_sol_with(v, t, opts)
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => sequence(tuple)?,
+            };
+            Ok(DynSolValue::CustomStruct {
+                name: name.clone(),
+                prop_names: prop_names.clone(),
+                tuple: values,
+            })
+        }
+    }
+}
+
+/// Converts a decoded value to JSON, using the `components` of the ABI
+/// parameter it was decoded from to turn tuples into objects keyed by their
+/// component names, at any depth. Falls back to positional arrays for tuples
+/// with unnamed components. Keys are sorted by the caller, once the whole
+/// object is built.
+pub(crate) fn param_to_json(val: DynSolValue, components: &[Param], opts: &OutputOptions) -> Value {
+    if components.is_empty() {
+        return to_json(val, opts);
+    }
+    let convert = |a: Vec<DynSolValue>| -> Vec<Value> {
+        a.into_iter()
+            .zip(components)
+            .map(|(v, c)| param_to_json(v, &c.components, opts))
+            .collect()
+    };
+
+    match val {
+        DynSolValue::Tuple(a) if components.iter().all(|c| !c.name.is_empty()) => {
+            let names = components.iter().map(|c| opts.key_case.convert(&c.name));
+            Value::Object(names.zip(convert(a)).collect())
+        }
+        DynSolValue::Tuple(a) => Value::Array(convert(a)),
+        DynSolValue::CustomStruct {
+            name,
+            prop_names,
+            tuple,
+        } => struct_to_json(name, prop_names, convert(tuple), opts),
+        DynSolValue::Array(a) | DynSolValue::FixedArray(a) => Value::Array(
+            a.into_iter()
+                .map(|v| param_to_json(v, components, opts))
+                .collect(),
+        ),
+        val => to_json(val, opts),
+    }
+}
+
+/// [`param_to_json`] for the top-level parameter `name`, applying
+/// [`OutputOptions::decimals`].
+pub(crate) fn field_to_json(
+    name: &str,
+    val: DynSolValue,
+    components: &[Param],
+    opts: &OutputOptions,
+) -> Value {
+    match (opts.decimals.get(name), val) {
+        (Some(&decimals), DynSolValue::Uint(amount, _)) => Value::String(scale(amount, decimals)),
+        (_, val) => param_to_json(val, components, opts),
+    }
+}
+
+/// Inverse of [`field_to_json`]: parameters scaled by
+/// [`OutputOptions::decimals`] are multiplied back by `10^decimals`.
+pub(crate) fn json_to_field(
+    name: &str,
+    value: &Value,
+    ty: &DynSolType,
+    components: &[Param],
+    opts: &OutputOptions,
+) -> Result<DynSolValue, ConversionError> {
+    let (Some(&decimals), DynSolType::Uint(bits)) = (opts.decimals.get(name), ty) else {
+        return json_to_param(value, ty, components, opts);
+    };
+    let scaled = value
+        .as_str()
+        .ok_or_else(|| ConversionError::TypeMismatch {
+            expected: ty.to_string(),
+            found: value.clone(),
+        })?;
+    match unscale(scaled, decimals) {
+        Some(amount) if amount.bit_len() <= *bits => Ok(DynSolValue::Uint(amount, *bits)),
+        _ => Err(ConversionError::OutOfRange {
+            ty: ty.to_string(),
+            value: scaled.to_string(),
+        }),
+    }
+}
+
+/// Inverse of [`scale`]. `None` if `scaled` isn't a non-negative decimal,
+/// has more than `decimals` fractional digits or doesn't fit in 256 bits.
+fn unscale(scaled: &str, decimals: u8) -> Option<U256> {
+    let (whole, fraction) = scaled.split_once('.').unwrap_or((scaled, ""));
+    let decimals = decimals as usize;
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !digits(whole) || !digits(fraction) || fraction.len() > decimals {
+        return None;
+    }
+    let digits = format!("{whole}{fraction:0<decimals$}");
+    U256::from_str_radix(&digits, 10).ok()
+}
+
+/// Formats `amount / 10^decimals` exactly, without trailing zeros.
+fn scale(amount: U256, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = amount.to_string();
+    let digits = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    match fraction.trim_end_matches('0') {
+        "" => whole.to_string(),
+        fraction => format!("{whole}.{fraction}"),
+    }
+}
+
+/// Inverse of [`param_to_json`]: converts JSON back into a value of type `ty`,
+/// reading tuples emitted as objects keyed by their `components` names.
+pub(crate) fn json_to_param(
+    value: &Value,
+    ty: &DynSolType,
+    components: &[Param],
+    opts: &OutputOptions,
+) -> Result<DynSolValue, ConversionError> {
+    let fields = |types: &[DynSolType], map: &Map<String, Value>| {
+        types
+            .iter()
+            .zip(components)
+            .map(|(t, c)| {
+                let key = opts.key_case.convert(&c.name);
+                let v = map.get(&key).ok_or_else(|| ConversionError::MissingField {
+                    ty: ty.to_string(),
+                    name: key.clone(),
+                })?;
+                json_to_param(v, t, &c.components, opts)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    match (ty, value) {
+        (DynSolType::Tuple(types), Value::Object(map)) if !components.is_empty() => {
+            fields(types, map).map(DynSolValue::Tuple)
+        }
+        (
+            DynSolType::CustomStruct {
+                name,
+                prop_names,
+                tuple,
+            },
+            Value::Object(map),
+        ) if !components.is_empty() => Ok(DynSolValue::CustomStruct {
+            name: name.clone(),
+            prop_names: prop_names.clone(),
+            tuple: fields(tuple, map)?,
+        }),
+        (DynSolType::Tuple(types), Value::Array(items)) if items.len() == types.len() => items
+            .iter()
+            .zip(types)
+            .zip(components)
+            .map(|((v, t), c)| json_to_param(v, t, &c.components, opts))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::Tuple),
+        (DynSolType::Array(inner), Value::Array(items)) => items
+            .iter()
+            .map(|v| json_to_param(v, inner, components, opts))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::Array),
+        (DynSolType::FixedArray(inner, len), Value::Array(items)) if items.len() == *len => items
+            .iter()
+            .map(|v| json_to_param(v, inner, components, opts))
+            .collect::<Result<_, _>>()
+            .map(DynSolValue::FixedArray),
+        _ => json_to_dyn_sol_with(value, ty, opts),
+    }
+}
+
+/// Builds the object for a struct, tagging it with its Solidity type name if
+/// [`OutputOptions::include_struct_type`] is set.
+fn struct_to_json(
+    name: String,
+    prop_names: Vec<String>,
+    values: Vec<Value>,
+    opts: &OutputOptions,
+) -> Value {
+    let names = prop_names.iter().map(|p| opts.key_case.convert(p));
+    let mut map: Map<String, Value> = names.zip(values).collect();
+    if opts.include_struct_type {
+        map.entry(STRUCT_TYPE_KEY).or_insert(Value::String(name));
+    }
+    Value::Object(map)
+}
+
+/// Rebuilds every object in `value` with its keys in lexicographic order,
+/// at any depth.
+pub(crate) fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(sort_map(map)),
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// [`sort_keys`] for an object.
+pub(crate) fn sort_map(map: Map<String, Value>) -> Map<String, Value> {
+    let mut entries: Vec<_> = map.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+        .into_iter()
+        .map(|(k, v)| (k, sort_keys(v)))
+        .collect()
+}
+
+/// Flattens nested objects and arrays into a single-level object with keys
+/// joined by `separator`.
+pub(crate) fn flatten(map: Map<String, Value>, separator: char) -> Map<String, Value> {
+    let mut flat = Map::new();
+    for (key, value) in map {
+        flatten_into(key, value, separator, &mut flat);
+    }
+    flat
+}
+
+fn flatten_into(prefix: String, value: Value, separator: char, flat: &mut Map<String, Value>) {
+    let children: Vec<(String, Value)> = match value {
+        Value::Object(map) if !map.is_empty() => map.into_iter().collect(),
+        Value::Array(items) if !items.is_empty() => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), v))
+            .collect(),
+        leaf => {
+            flat.insert(prefix, leaf);
+            return;
+        }
+    };
+    for (key, value) in children {
+        flatten_into(format!("{prefix}{separator}{key}"), value, separator, flat);
+    }
+}
+
+/// Inverse of [`flatten`] for the parameter under `prefix` of type `ty` with
+/// `components`, rebuilding its value from the keys of `flat` like
+/// [`param_to_json`] shapes it. `None` if a leaf is missing.
+pub(crate) fn unflatten(
+    flat: &Map<String, Value>,
+    prefix: &str,
+    ty: &DynSolType,
+    components: &[Param],
+    opts: &OutputOptions,
+) -> Option<Value> {
+    if let Some(value) = flat.get(prefix) {
+        return Some(value.clone());
+    }
+    let child = |key: &str, ty: &DynSolType, components: &[Param]| {
+        let prefix = format!("{prefix}{}{key}", opts.separator);
+        unflatten(flat, &prefix, ty, components, opts)
+    };
+    let member = |i: usize| components.get(i).map_or(&[][..], |c| &c.components);
+    let object = |names: &mut dyn Iterator<Item = &String>, types: &[DynSolType]| {
+        names
+            .zip(types)
+            .enumerate()
+            .map(|(i, (name, t))| {
+                let key = opts.key_case.convert(name);
+                Some((key.clone(), child(&key, t, member(i))?))
+            })
+            .collect::<Option<Map<_, _>>>()
+            .map(Value::Object)
+    };
+
+    match ty {
+        DynSolType::CustomStruct {
+            prop_names, tuple, ..
+        } => object(&mut prop_names.iter(), tuple),
+        DynSolType::Tuple(types)
+            if !components.is_empty() && components.iter().all(|c| !c.name.is_empty()) =>
+        {
+            object(&mut components.iter().map(|c| &c.name), types)
+        }
+        DynSolType::Tuple(types) => types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| child(&i.to_string(), t, member(i)))
+            .collect::<Option<_>>()
+            .map(Value::Array),
+        DynSolType::FixedArray(inner, len) => (0..*len)
+            .map(|i| child(&i.to_string(), inner, components))
+            .collect::<Option<_>>()
+            .map(Value::Array),
+        DynSolType::Array(inner) => {
+            // Empty arrays are kept as a value, so a present array has an
+            // element at index 0.
+            let items: Vec<_> = (0..)
+                .map_while(|i: usize| child(&i.to_string(), inner, components))
+                .collect();
+            (!items.is_empty()).then_some(Value::Array(items))
+        }
+        _ => None,
+    }
+}
+
+/// Appends the leaves of `value`, decoded from a parameter of type `ty` with
+/// `components`, to `columns` like [`flatten`] does, keeping tuple and struct
+/// members in ABI order rather than in the order of the object's keys. Keys
+/// the ABI doesn't name, such as `__type` or `hashed`, follow the members.
+pub(crate) fn columns_into(
+    prefix: String,
+    value: Value,
+    ty: Option<&DynSolType>,
+    components: &[Param],
+    opts: &OutputOptions,
+    columns: &mut Vec<(String, Value)>,
+) {
+    let separator = opts.separator;
+    let members = match ty {
+        Some(DynSolType::Tuple(types) | DynSolType::CustomStruct { tuple: types, .. }) => types,
+        _ => &[][..],
+    };
+    match value {
+        Value::Object(mut map) if !map.is_empty() => {
+            for (member, ty) in components.iter().zip(members) {
+                let key = opts.key_case.convert(&member.name);
+                if let Some(value) = map.remove(&key) {
+                    let prefix = format!("{prefix}{separator}{key}");
+                    columns_into(prefix, value, Some(ty), &member.components, opts, columns);
+                }
+            }
+            for (key, value) in map {
+                let prefix = format!("{prefix}{separator}{key}");
+                columns_into(prefix, value, None, &[], opts, columns);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, value) in items.into_iter().enumerate() {
+                let prefix = format!("{prefix}{separator}{i}");
+                match ty {
+                    Some(DynSolType::Array(inner) | DynSolType::FixedArray(inner, _)) => {
+                        columns_into(prefix, value, Some(inner), components, opts, columns)
+                    }
+                    // Tuples with unnamed components are positional arrays.
+                    _ => {
+                        let member = components.get(i).map_or(&[][..], |c| &c.components);
+                        columns_into(prefix, value, members.get(i), member, opts, columns)
+                    }
+                }
+            }
+        }
+        leaf => columns.push((prefix, leaf)),
+    }
+}
+
+/// Fails if any of `values` is nested beyond `max_depth`.
+pub(crate) fn check_depth<'v>(
+    values: impl IntoIterator<Item = &'v DynSolValue>,
+    max_depth: usize,
+) -> Result<(), ParsingError> {
+    match values.into_iter().map(nesting_depth).max() {
+        Some(depth) if depth > max_depth => Err(ParsingError::DepthExceeded { depth, max_depth }),
+        _ => Ok(()),
+    }
+}
+
+/// The nesting depth of arrays, tuples and structs in `value`, where scalars
+/// have a depth of zero. Walks the value with an explicit stack, so that it
+/// can be called before recursing into arbitrarily deep values.
+fn nesting_depth(value: &DynSolValue) -> usize {
+    let mut deepest = 0;
+    let mut stack = vec![(value, 0)];
+    while let Some((value, depth)) = stack.pop() {
+        let children = match value {
+            DynSolValue::Array(items)
+            | DynSolValue::FixedArray(items)
+            | DynSolValue::Tuple(items)
+            | DynSolValue::CustomStruct { tuple: items, .. } => items,
+            _ => continue,
+        };
+        deepest = deepest.max(depth + 1);
+        stack.extend(children.iter().map(|child| (child, depth + 1)));
+    }
+    deepest
+}
+
+/// Formats `value` as minimal `0x`-prefixed hex, like a JSON-RPC quantity.
+fn quantity(value: U256) -> String {
+    let digits = format!("{value:x}");
+    match digits.trim_start_matches('0') {
+        "" => "0x0".to_string(),
+        trimmed => format!("0x{trimmed}"),
+    }
+}
+
+/// The two's complement of `int` at `bits` wide.
+fn twos_complement(int: I256, bits: usize) -> U256 {
+    let raw = int.into_raw();
+    if bits >= 256 {
+        raw
+    } else {
+        raw & ((U256::from(1) << bits) - U256::from(1))
+    }
+}
+
+/// Inverse of [`twos_complement`].
+fn sign_extend(raw: U256, bits: usize) -> I256 {
+    if bits < 256 && raw.bit(bits - 1) {
+        I256::from_raw(raw | (U256::MAX << bits))
+    } else {
+        I256::from_raw(raw)
+    }
+}
+
+/// Emits a decimal integer as a string, or as a full precision JSON number
+/// when the `arbitrary-precision` feature is enabled.
+fn decimal(digits: String) -> Value {
+    #[cfg(feature = "arbitrary-precision")]
+    if let Ok(number) = digits.parse::<serde_json::Number>() {
+        return Value::Number(number);
+    }
+    Value::String(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{B256, I256, U256};
+
+    #[test]
+    fn bytes_encodings() {
+        let bytes = DynSolValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef]);
+        let fixed = DynSolValue::FixedBytes(B256::left_padding_from(&[0xAB]), 32);
+
+        let base64 = OutputOptions::default();
+        assert_eq!(dyn_sol_to_json_with(bytes.clone(), &base64), "3q2+7w==");
+        assert_eq!(
+            dyn_sol_to_json_with(fixed.clone(), &base64),
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAKs="
+        );
+
+        let hex = OutputOptions {
+            bytes_encoding: BytesEncoding::Hex,
+            ..Default::default()
+        };
+        assert_eq!(dyn_sol_to_json_with(bytes.clone(), &hex), "0xdeadbeef");
+        assert_eq!(
+            dyn_sol_to_json_with(fixed.clone(), &hex),
+            "0x00000000000000000000000000000000000000000000000000000000000000ab"
+        );
+
+        let fixed_hex = OutputOptions {
+            fixed_bytes_encoding: Some(BytesEncoding::Hex),
+            ..Default::default()
+        };
+        assert_eq!(dyn_sol_to_json_with(bytes, &fixed_hex), "3q2+7w==");
+        assert_eq!(
+            dyn_sol_to_json_with(fixed, &fixed_hex),
+            "0x00000000000000000000000000000000000000000000000000000000000000ab"
+        );
+    }
+
+    #[test]
+    fn key_cases() {
+        let camel = [
+            ("packet_sequence", "packetSequence"),
+            ("packetSequence", "packetSequence"),
+            ("tokenID", "tokenId"),
+            ("IBCPacket", "ibcPacket"),
+            ("erc20Token", "erc20Token"),
+            ("_value", "_value"),
+            ("_token_id", "_tokenId"),
+            ("__", "__"),
+        ];
+        for (name, expected) in camel {
+            assert_eq!(KeyCase::CamelCase.convert(name), expected, "{name}");
+        }
+
+        let snake = [
+            ("packetSequence", "packet_sequence"),
+            ("packet_sequence", "packet_sequence"),
+            ("tokenID", "token_id"),
+            ("IBCPacket", "ibc_packet"),
+            ("erc20Token", "erc20_token"),
+            ("_tokenId", "_token_id"),
+            ("value_", "value_"),
+        ];
+        for (name, expected) in snake {
+            assert_eq!(KeyCase::SnakeCase.convert(name), expected, "{name}");
+        }
+        assert_eq!(KeyCase::AsIs.convert("packet_Sequence"), "packet_Sequence");
+    }
+
+    #[test]
+    fn address_casing() {
+        let address: Address = "0xea610b1153477720748dc13ed378003941d84fab"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            dyn_sol_to_json(DynSolValue::Address(address)),
+            "0xEA610B1153477720748DC13ED378003941d84fAB"
+        );
+
+        let lowercase = OutputOptions {
+            address_case: AddressCase::Lowercase,
+            ..Default::default()
+        };
+        assert_eq!(
+            dyn_sol_to_json_with(DynSolValue::Address(address), &lowercase),
+            "0xea610b1153477720748dc13ed378003941d84fab"
+        );
+
+        let bare = OutputOptions {
+            address_prefix: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            dyn_sol_to_json_with(DynSolValue::Address(address), &bare),
+            "EA610B1153477720748DC13ED378003941d84fAB"
+        );
+        let bare_lowercase = OutputOptions {
+            address_prefix: false,
+            ..lowercase
+        };
+        let json = dyn_sol_to_json_with(DynSolValue::Address(address), &bare_lowercase);
+        assert_eq!(json, "ea610b1153477720748dc13ed378003941d84fab");
+        assert_eq!(
+            json_to_dyn_sol(&json, &DynSolType::Address).unwrap(),
+            DynSolValue::Address(address)
+        );
+    }
+
+    #[test]
+    fn amounts_are_scaled_by_decimals() {
+        let amount = |n: u128| U256::from(n);
+        assert_eq!(scale(amount(1_500_000_000_000_000_000), 18), "1.5");
+        assert_eq!(scale(amount(1), 18), "0.000000000000000001");
+        assert_eq!(scale(amount(0), 18), "0");
+        assert_eq!(scale(amount(100), 2), "1");
+        assert_eq!(scale(amount(123), 0), "123");
+        assert_eq!(
+            scale(U256::MAX, 77),
+            "1.15792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+
+        let opts = OutputOptions {
+            decimals: [("value".to_string(), 6)].into(),
+            ..Default::default()
+        };
+        let uint = DynSolValue::Uint(amount(2_500_000), 256);
+        assert_eq!(field_to_json("value", uint.clone(), &[], &opts), "2.5");
+        assert_eq!(
+            field_to_json("other", uint.clone(), &[], &opts),
+            dyn_sol_to_json(uint.clone())
+        );
+
+        let ty = DynSolType::Uint(256);
+        let back = |scaled: &str| json_to_field("value", &scaled.into(), &ty, &[], &opts);
+        assert_eq!(back("2.5").unwrap(), uint);
+        assert_eq!(
+            back("3").unwrap(),
+            DynSolValue::Uint(amount(3_000_000), 256)
+        );
+        assert_eq!(back("0.000001").unwrap(), DynSolValue::Uint(amount(1), 256));
+        for invalid in ["0.0000001", "-1", ".5", "1e6", "0x10"] {
+            assert!(matches!(
+                back(invalid),
+                Err(ConversionError::OutOfRange { .. })
+            ));
+        }
+        let narrow = DynSolType::Uint(8);
+        assert!(json_to_field("value", &"0.000256".into(), &narrow, &[], &opts).is_err());
+    }
+
+    #[test]
+    fn fixed_bytes_length_is_checked() {
+        let ty = DynSolType::FixedBytes(4);
+        let selector = B256::right_padding_from(&[0xde, 0xad, 0xbe, 0xef]);
+        let json = dyn_sol_to_json(DynSolValue::FixedBytes(selector, 4));
+        assert_eq!(
+            json_to_dyn_sol(&json, &ty).unwrap(),
+            DynSolValue::FixedBytes(selector, 4)
+        );
+
+        // A `bytes4` carrying six significant bytes.
+        let word = B256::right_padding_from(&[0xde, 0xad, 0xbe, 0xef, 0x01, 0x02]);
+        let mismatched = DynSolValue::FixedBytes(word, 4);
+        let json = dyn_sol_to_json(mismatched.clone());
+        assert!(matches!(
+            json_to_dyn_sol(&json, &ty),
+            Err(ConversionError::LengthMismatch {
+                expected: 4,
+                found: 6,
+                ..
+            })
+        ));
+        let lenient = OutputOptions {
+            strict: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            json_to_dyn_sol_with(&json, &ty, &lenient).unwrap(),
+            mismatched
+        );
+
+        // Converting the value itself fails the same way.
+        let nested = DynSolValue::Tuple(vec![DynSolValue::Array(vec![mismatched.clone()])]);
+        assert!(matches!(
+            try_dyn_sol_to_json_with(nested, &OutputOptions::default()),
+            Err(ParsingError::Conversion(ConversionError::LengthMismatch {
+                expected: 4,
+                found: 6,
+                ..
+            }))
+        ));
+        assert_eq!(
+            try_dyn_sol_to_json_with(mismatched.clone(), &lenient).unwrap(),
+            json
+        );
+        assert!(try_dyn_sol_to_json_with(
+            DynSolValue::FixedBytes(selector, 4),
+            &OutputOptions::default()
+        )
+        .is_ok());
+
+        let short = Value::String("AQI=".to_owned());
+        assert!(matches!(
+            json_to_dyn_sol(&short, &ty),
+            Err(ConversionError::LengthMismatch { found: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn nesting_depth_is_measured_without_recursion() {
+        let uint = DynSolValue::Uint(U256::from(1), 256);
+        assert_eq!(nesting_depth(&uint), 0);
+        assert_eq!(nesting_depth(&DynSolValue::Array(vec![])), 1);
+
+        let mut value = uint.clone();
+        for i in 0..10_000 {
+            value = if i % 2 == 0 {
+                DynSolValue::Array(vec![value])
+            } else {
+                DynSolValue::Tuple(vec![uint.clone(), value])
+            };
+        }
+        assert_eq!(nesting_depth(&value), 10_000);
+
+        let opts = OutputOptions::default();
+        let mut deep = uint.clone();
+        for _ in 0..200 {
+            deep = DynSolValue::Array(vec![deep]);
+        }
+        assert!(matches!(
+            try_dyn_sol_to_json_with(deep, &opts),
+            Err(ParsingError::DepthExceeded {
+                depth: 200,
+                max_depth: 128
+            })
+        ));
+        let shallow = DynSolValue::Array(vec![uint.clone()]);
+        assert_eq!(
+            try_dyn_sol_to_json_with(shallow.clone(), &opts).unwrap(),
+            dyn_sol_to_json(shallow)
+        );
+
+        // Dropping is recursive too, so unwind the value iteratively.
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            if let DynSolValue::Array(items) | DynSolValue::Tuple(items) = value {
+                stack.extend(items);
+            }
+        }
+    }
+
+    #[test]
+    fn bytes_as_arrays() {
+        let opts = OutputOptions {
+            fixed_bytes_as_array: true,
+            bytes_encoding: BytesEncoding::Hex,
+            ..Default::default()
+        };
+        let word = B256::repeat_byte(0xde);
+        let json = dyn_sol_to_json_with(DynSolValue::FixedBytes(word, 32), &opts);
+        assert_eq!(json, Value::Array(vec![Value::from(0xde); 32]));
+        assert_eq!(
+            json_to_dyn_sol_with(&json, &DynSolType::FixedBytes(32), &opts).unwrap(),
+            DynSolValue::FixedBytes(word, 32)
+        );
+
+        let selector = DynSolValue::FixedBytes(B256::right_padding_from(&[0xde, 0xad]), 2);
+        let json = dyn_sol_to_json_with(selector.clone(), &opts);
+        assert_eq!(json, serde_json::json!([222, 173]));
+        assert_eq!(
+            json_to_dyn_sol(&json, &DynSolType::FixedBytes(2)).unwrap(),
+            selector
+        );
+
+        let bytes = DynSolValue::Bytes(vec![1, 2, 3]);
+        let json = dyn_sol_to_json_with(bytes.clone(), &opts);
+        assert_eq!(json, serde_json::json!([1, 2, 3]));
+        assert_eq!(json_to_dyn_sol(&json, &DynSolType::Bytes).unwrap(), bytes);
+        assert!(json_to_dyn_sol(&serde_json::json!([256]), &DynSolType::Bytes).is_err());
+    }
+
+    #[test]
+    fn custom_formatter_overrides_values() {
+        let named: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let ens = |value: &DynSolValue| match value {
+            DynSolValue::Address(a) if *a == named => Some(Value::from("vitalik.eth")),
+            _ => None,
+        };
+        let value = DynSolValue::Tuple(vec![
+            DynSolValue::Array(vec![
+                DynSolValue::Address(named),
+                DynSolValue::Address(Address::ZERO),
+            ]),
+            DynSolValue::Bool(true),
+        ]);
+        let json = dyn_sol_to_json_with_formatter(value.clone(), &OutputOptions::default(), ens);
+        assert_eq!(
+            json,
+            serde_json::json!([["vitalik.eth", Address::ZERO.to_checksum(None)], true])
+        );
+
+        let fallback =
+            dyn_sol_to_json_with_formatter(value.clone(), &OutputOptions::default(), |_| None);
+        assert_eq!(fallback, dyn_sol_to_json(value));
+    }
+
+    #[test]
+    fn struct_members_take_precedence_over_type_name() {
+        let opts = OutputOptions {
+            include_struct_type: true,
+            ..Default::default()
+        };
+        let value = DynSolValue::CustomStruct {
+            name: "Tagged".into(),
+            prop_names: vec!["__type".into()],
+            tuple: vec![DynSolValue::String("member".into())],
+        };
+        assert_eq!(
+            dyn_sol_to_json_with(value, &opts),
+            serde_json::json!({ "__type": "member" })
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let ty: DynSolType = "(uint8,int16,bytes4,bytes,address[],string)"
+            .parse()
+            .unwrap();
+        let ty = match ty {
+            DynSolType::Tuple(tuple) => DynSolType::CustomStruct {
+                name: "Packet".into(),
+                prop_names: ["a", "b", "c", "d", "e", "f"].map(String::from).to_vec(),
+                tuple,
+            },
+            _ => unreachable!(),
+        };
+        let value = DynSolValue::CustomStruct {
+            name: "Packet".into(),
+            prop_names: ["a", "b", "c", "d", "e", "f"].map(String::from).to_vec(),
+            tuple: vec![
+                DynSolValue::Uint(U256::from(255), 8),
+                DynSolValue::Int(I256::try_from(-32768).unwrap(), 16),
+                DynSolValue::FixedBytes(B256::right_padding_from(&[1, 2, 3, 4]), 4),
+                DynSolValue::Bytes(vec![0xde, 0xad]),
+                DynSolValue::Array(vec![DynSolValue::Address(Address::repeat_byte(0xab))]),
+                DynSolValue::String("hello".into()),
+            ],
+        };
+        for opts in [
+            OutputOptions::default(),
+            OutputOptions {
+                bytes_encoding: BytesEncoding::Hex,
+                ..Default::default()
+            },
+            OutputOptions {
+                fixed_bytes_encoding: Some(BytesEncoding::Hex),
+                numbers_as_json_when_small: true,
+                address_case: AddressCase::Lowercase,
+                include_struct_type: true,
+                ..Default::default()
+            },
+        ] {
+            let json = dyn_sol_to_json_with(value.clone(), &opts);
+            assert_eq!(json_to_dyn_sol_with(&json, &ty, &opts).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn json_conversion_errors() {
+        assert!(matches!(
+            json_to_dyn_sol(&"256".into(), &DynSolType::Uint(8)),
+            Err(ConversionError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            json_to_dyn_sol(&"-129".into(), &DynSolType::Int(8)),
+            Err(ConversionError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            json_to_dyn_sol(&true.into(), &DynSolType::Address),
+            Err(ConversionError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            json_to_dyn_sol(
+                &serde_json::json!([true]),
+                &DynSolType::FixedArray(Box::new(DynSolType::Bool), 2)
+            ),
+            Err(ConversionError::LengthMismatch {
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+        let ty = DynSolType::CustomStruct {
+            name: "S".into(),
+            prop_names: vec!["x".into()],
+            tuple: vec![DynSolType::Bool],
+        };
+        assert!(matches!(
+            json_to_dyn_sol(&serde_json::json!({}), &ty),
+            Err(ConversionError::MissingField { name, .. }) if name == "x"
+        ));
+    }
+
+    #[test]
+    fn flattening() {
+        let nested = serde_json::json!({
+            "a": { "b": [1, { "c": true }], "empty": [] },
+            "d": "x",
+        });
+        let Value::Object(map) = nested else {
+            unreachable!()
+        };
+        assert_eq!(
+            Value::Object(flatten(map, '_')),
+            serde_json::json!({ "a_b_0": 1, "a_b_1_c": true, "a_empty": [], "d": "x" })
+        );
+    }
+
+    #[test]
+    fn hex_integers() {
+        let opts = OutputOptions {
+            int_encoding: IntEncoding::Hex,
+            ..Default::default()
+        };
+        let int = |i: i64, bits| DynSolValue::Int(I256::try_from(i).unwrap(), bits);
+        let cases = [
+            (DynSolValue::Uint(U256::from(500), 256), "0x1f4"),
+            (DynSolValue::Uint(U256::ZERO, 8), "0x0"),
+            (int(0, 256), "0x0"),
+            (int(500, 16), "0x1f4"),
+            (int(-1, 8), "0xff"),
+            (int(-500, 16), "0xfe0c"),
+            (
+                int(-1, 256),
+                "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+        ];
+        for (value, hex) in cases {
+            let json = dyn_sol_to_json_with(value.clone(), &opts);
+            assert_eq!(json, hex);
+            let ty = value.as_type().unwrap();
+            assert_eq!(json_to_dyn_sol(&json, &ty).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn small_numbers_as_json() {
+        let opts = OutputOptions {
+            numbers_as_json_when_small: true,
+            ..Default::default()
+        };
+        let max = U256::from(u64::MAX);
+        assert_eq!(
+            dyn_sol_to_json_with(DynSolValue::Uint(max, 256), &opts),
+            serde_json::json!(u64::MAX)
+        );
+        #[cfg(not(feature = "arbitrary-precision"))]
+        assert_eq!(
+            dyn_sol_to_json_with(DynSolValue::Uint(max + U256::from(1), 256), &opts),
+            "18446744073709551616"
+        );
+        assert_eq!(
+            dyn_sol_to_json_with(DynSolValue::Int(I256::try_from(-42).unwrap(), 256), &opts),
+            serde_json::json!(-42)
+        );
+        #[cfg(not(feature = "arbitrary-precision"))]
+        assert_eq!(
+            dyn_sol_to_json_with(
+                DynSolValue::Int(I256::try_from(i64::MIN as i128 - 1).unwrap(), 256),
+                &opts
+            ),
+            "-9223372036854775809"
+        );
+
+        // The default keeps every integer a string.
+        #[cfg(not(feature = "arbitrary-precision"))]
+        assert_eq!(dyn_sol_to_json(DynSolValue::Uint(U256::from(1), 256)), "1");
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn arbitrary_precision_numbers() {
+        let json = dyn_sol_to_json(DynSolValue::Uint(U256::MAX, 256));
+        let serialized = serde_json::to_string(&json).unwrap();
+        assert_eq!(serialized, U256::MAX.to_string());
+        assert_eq!(serialized.len(), 78);
+    }
+}
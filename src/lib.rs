@@ -1,25 +1,377 @@
-use alloy_dyn_abi::DynSolValue;
-use alloy_dyn_abi::EventExt;
-use alloy_json_abi::JsonAbi;
-use alloy_primitives::B256;
-use ethers::core::abi::ethabi::ethereum_types::H256;
-use ethers::core::types::Log;
-use serde::{Deserialize, Serialize};
+//! Dynamically decode Ethereum logs, revert data and calldata into JSON using
+//! a JSON ABI.
+//!
+//! # Features
+//!
+//! - `std` (default): link the standard library. Without it the crate is
+//!   `no_std` and only needs `alloc`; decoding alloy's log types and
+//!   converting values to JSON keep working.
+//! - `ethers` (default, implies `std`): decode `ethers::core::types::Log`.
+//! - `rayon`: [`Parser::par_parse_all`].
+//! - `stream`: [`Parser::parse_stream`], also without `std`.
+//! - `msgpack` (implies `std`): [`KeyedEvent::to_bytes`] and
+//!   [`KeyedEvent::from_bytes`].
+//! - `arbitrary-precision`: emit large integers as JSON numbers.
+//! - `capture-input-on-error`: keep the input which failed to decode in
+//!   [`ParsingError::DecodingFailed`].
+//!
+//! # WebAssembly
+//!
+//! Disable the default features to build for `wasm32-unknown-unknown`, as
+//! ethers doesn't support it; keep `std` unless the target lacks it:
+//!
+//! ```text
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features std
+//! ```
+//!
+//! Nothing in the decoding path uses threads or clocks. Decoded events
+//! serialize to JSON strings, or convert with `serde-wasm-bindgen`, to be
+//! handed to JavaScript.
+//!
+//! The example below only uses the alloy-native path, so it also runs with
+//! `--no-default-features`:
+//!
+//! ```
+//! use alloy_dyn_parser::Parser;
+//! use alloy_json_abi::JsonAbi;
+//! use alloy_primitives::{Address, LogData, U256};
+//!
+//! let abi = JsonAbi::parse(["event Transfer(address indexed from, address indexed to, uint256 value)"]).unwrap();
+//! let event = abi.events().next().unwrap();
+//! let log = LogData::new_unchecked(
+//!     vec![event.selector(), Address::ZERO.into_word(), Address::ZERO.into_word()],
+//!     U256::from(1).to_be_bytes_vec().into(),
+//! );
+//! let decoded = Parser::new(&abi).parse(&log).unwrap();
+//! assert_eq!(decoded.to_string(), format!("Transfer {{ from: {0}, to: {0}, value: 1 }}", Address::ZERO));
+//! ```
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use alloy_dyn_abi::{DecodedEvent as DynDecodedEvent, DynSolEvent, DynSolType, DynSolValue};
+use alloy_dyn_abi::{EventExt, JsonAbiExt, ResolveSolType};
+use alloy_json_abi::{Error as AbiError, Event, EventParam, Function, JsonAbi, Param};
+use alloy_primitives::{Address, Bytes, Selector, B256};
+use core::fmt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{Map, Value};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 use thiserror::Error;
 
+mod builder;
+mod erc20;
+mod json;
+mod log;
+mod owned;
+mod schema;
+
+pub use builder::ParserBuilder;
+pub use erc20::{Approval, Transfer};
+pub use json::{
+    dyn_sol_to_json, dyn_sol_to_json_with, dyn_sol_to_json_with_formatter, json_to_dyn_sol,
+    json_to_dyn_sol_with, try_dyn_sol_to_json_with, try_dyn_sol_to_json_with_formatter,
+    AddressCase, BytesEncoding, ConversionError, IntEncoding, KeyCase, OutputOptions,
+    UnnamedParameters,
+};
+pub use log::{EventLog, LogMeta};
+
+use log::RpcLog;
+pub use owned::OwnedParser;
+
+use json::{
+    check_depth, columns_into, field_to_json, flatten, json_to_field, param_key, sort_keys,
+    sort_map,
+};
+
 pub struct Parser<'a> {
-    abi: &'a JsonAbi,
+    abis: &'a [JsonAbi],
+    /// Events keyed by their topic0 selector, computed once in [`Parser::new`].
+    /// Holds more than one event when several ABIs define the same selector
+    /// differently.
+    events: HashMap<B256, Vec<&'a Event>>,
+    /// Anonymous events, which have no selector and are matched by shape.
+    anonymous: Vec<&'a Event>,
+    /// Every event, anonymous or not, keyed by name. Also computed once in
+    /// [`Parser::new`]; holds overloads in ABI order.
+    names: HashMap<String, Vec<&'a Event>>,
+    /// Custom errors keyed by their 4-byte selector.
+    errors: HashMap<Selector, &'a AbiError>,
+    /// Functions keyed by their 4-byte selector.
+    functions: HashMap<Selector, &'a Function>,
+    options: OutputOptions,
+}
+
+/// The number of definitions a [`Parser`] can decode, returned by
+/// [`Parser::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParserStats {
+    /// Events matched by their topic0 selector.
+    pub events: usize,
+    /// Custom errors, besides the builtin `Error(string)` and
+    /// `Panic(uint256)`.
+    pub errors: usize,
+    pub functions: usize,
+    pub anonymous_events: usize,
 }
 
 /// A decoded event which is self-describing through String keys.
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// The default value is an empty buffer for [`Parser::parse_into`]. Events
+/// compare equal when every field does, including `meta`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct KeyedEvent {
     /// The name of the event.
     name: String,
 
+    /// The topic0 selector of the matched event. For errors and function
+    /// calls the 4-byte selector occupies the leading bytes and the rest is
+    /// zeroed.
+    selector: B256,
+
+    /// The canonical signature the selector is hashed from, e.g.
+    /// `Transfer(address,address,uint256)`. Only populated when
+    /// [`OutputOptions::include_signature`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+
     /// The data of the emitted event, both indexed and body.
     data: serde_json::Value,
+
+    /// The Solidity type of every parameter, keyed by parameter rather than
+    /// by flattened or split field. Only populated when
+    /// [`OutputOptions::include_types`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    types: Option<Map<String, Value>>,
+
+    /// Provenance of the log the event was decoded from. Only populated by
+    /// [`Parser::parse_with_meta`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    meta: Option<LogMeta>,
+
+    /// Whether the log's data was zero-padded to a word boundary before it
+    /// could be decoded. Only ever set with [`OutputOptions::lenient_data`].
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    padded: bool,
+
+    /// Whether the log only decoded without validation. Only ever set when
+    /// [`OutputOptions::strict`] is disabled.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    non_strict: bool,
+
+    /// Topics beyond those the event definition has indexed inputs for, e.g.
+    /// when decoding with a minimized ABI. Only ever set when
+    /// [`OutputOptions::strict`] is disabled, in which case such logs are
+    /// decoded from the leading topics instead of failing.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extra_topics: Vec<B256>,
+
+    /// Every topic of the log, including topic0, as it was decoded. Only
+    /// populated when [`OutputOptions::include_raw_topics`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_topics: Option<Vec<B256>>,
+
+    /// The data of the log, as it was decoded. Only populated when
+    /// [`OutputOptions::include_raw_topics`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw_data: Option<Bytes>,
+}
+
+/// A decoded event borrowing its name from the ABI, returned by
+/// [`Parser::parse_ref`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyedEventRef<'a> {
+    /// The name of the event, as written in the ABI.
+    pub name: &'a str,
+
+    /// The data of the emitted event, both indexed and body.
+    pub data: Value,
+}
+
+impl KeyedEvent {
+    /// The event as a JSON object, identical to `serde_json::to_value(&event)`
+    /// but moving the decoded data rather than serializing it again.
+    pub fn into_value(mut self) -> Value {
+        let data = core::mem::take(&mut self.data);
+        let types = self.types.take();
+        self.object(data, types)
+    }
+
+    /// Encodes the event as MessagePack, a compact self-describing binary
+    /// format which, unlike bincode, can hold the free-form `data`. Fields
+    /// are written by name, so optional fields may be left out.
+    #[cfg(feature = "msgpack")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec_named(self)
+    }
+
+    /// Decodes an event encoded with [`KeyedEvent::to_bytes`].
+    #[cfg(feature = "msgpack")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+
+    /// [`KeyedEvent::into_value`] for a borrowed event, cloning the data.
+    pub fn as_value(&self) -> Value {
+        self.object(self.data.clone(), self.types.clone())
+    }
+
+    fn object(&self, data: Value, types: Option<Map<String, Value>>) -> Value {
+        let mut object = Map::new();
+        object.insert("name".to_string(), Value::String(self.name.clone()));
+        object.insert(
+            "selector".to_string(),
+            Value::String(self.selector.to_string()),
+        );
+        if let Some(signature) = &self.signature {
+            object.insert("signature".to_string(), Value::String(signature.clone()));
+        }
+        object.insert("data".to_string(), data);
+        if let Some(types) = types {
+            object.insert("types".to_string(), Value::Object(types));
+        }
+        if let Some(meta) = &self.meta {
+            let meta = serde_json::to_value(meta).expect("log metadata serializes to JSON");
+            object.insert("meta".to_string(), meta);
+        }
+        if self.padded {
+            object.insert("padded".to_string(), Value::Bool(true));
+        }
+        if self.non_strict {
+            object.insert("non_strict".to_string(), Value::Bool(true));
+        }
+        if !self.extra_topics.is_empty() {
+            let topics = self
+                .extra_topics
+                .iter()
+                .map(|t| Value::String(t.to_string()));
+            object.insert("extra_topics".to_string(), Value::Array(topics.collect()));
+        }
+        if let Some(topics) = &self.raw_topics {
+            let topics = topics.iter().map(|t| Value::String(t.to_string()));
+            object.insert("raw_topics".to_string(), Value::Array(topics.collect()));
+        }
+        if let Some(data) = &self.raw_data {
+            object.insert("raw_data".to_string(), Value::String(data.to_string()));
+        }
+        Value::Object(object)
+    }
+}
+
+/// Renders the event on a single line, e.g.
+/// `Transfer { from: 0x.., to: 0x.., value: 123 }`. Strings are written
+/// without quotes, so the output is meant for humans rather than parsing.
+impl fmt::Display for KeyedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.name)?;
+        display_value(f, &self.data)
+    }
+}
+
+fn display_value(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::String(s) => f.write_str(s),
+        Value::Array(items) => {
+            f.write_str("[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                display_value(f, item)?;
+            }
+            f.write_str("]")
+        }
+        Value::Object(map) if map.is_empty() => f.write_str("{}"),
+        Value::Object(map) => {
+            f.write_str("{ ")?;
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{key}: ")?;
+                display_value(f, item)?;
+            }
+            f.write_str(" }")
+        }
+        other => write!(f, "{other}"),
+    }
+}
+
+/// The outcome of decoding a log against a single event definition.
+struct Decoded {
+    event: DynDecodedEvent,
+    padded: bool,
+    strict: bool,
+    extra_topics: Vec<B256>,
+    raw: Option<(Vec<B256>, Bytes)>,
+    /// The definition's selector when it was looked up by the log's topic0,
+    /// sparing hashing its signature again.
+    selector: Option<B256>,
+}
+
+/// A decoded event holding the raw [`DynSolValue`]s, before any conversion to
+/// JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    /// The name of the event.
+    pub name: String,
+
+    /// The parameters, in the order of the ABI inputs.
+    pub values: Vec<DecodedField>,
+}
+
+/// A single parameter of a [`DecodedEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedField {
+    /// The name of the parameter in the ABI.
+    pub name: String,
+
+    /// The decoded value. Indexed `string`, `bytes`, arrays and structs are
+    /// the `bytes32` keccak256 hash of the original value.
+    pub value: DynSolValue,
+
+    /// Whether the value was read from a topic rather than the data.
+    pub indexed: bool,
+}
+
+impl DecodedEvent {
+    fn new(definition: &Event, decoded: DynDecodedEvent) -> Self {
+        let mut indexed = decoded.indexed.into_iter();
+        let mut body = decoded.body.into_iter();
+        let values = definition
+            .inputs
+            .iter()
+            .filter_map(|input| {
+                let value = if input.indexed {
+                    indexed.next()
+                } else {
+                    body.next()
+                };
+                Some(DecodedField {
+                    name: input.name.clone(),
+                    value: value?,
+                    indexed: input.indexed,
+                })
+            })
+            .collect();
+        Self {
+            name: definition.name.clone(),
+            values,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -27,122 +379,3821 @@ pub enum ParsingError {
     /// The name of the decoded event is not found in the ABI. This might
     /// indicate an ABI mismatch.
     #[error("event not found for given abi")]
-    UnknownEvent { selector: H256 },
+    UnknownEvent { selector: B256 },
+    /// The log carries no topics at all, so there is no selector to match
+    /// against the ABI. Usually caused by anonymous events or malformed log
+    /// dumps.
+    #[error("log emitted by {address:?} has no topic0")]
+    MissingTopic0 { address: Option<Address> },
+    /// No event with the requested name exists in the ABI.
+    #[error("no event named {name} in the abi")]
+    UnknownEventName { name: String },
+    /// None of the anonymous events in the ABI could decode the log.
+    #[error("no anonymous event in the abi matches the log")]
+    UnknownAnonymousEvent,
+    /// The log carries a different number of indexed topics (excluding topic0)
+    /// than the event has indexed inputs. This usually means the ABI is stale.
+    #[error("expected {expected} indexed topics, found {found}")]
+    TopicCountMismatch { expected: usize, found: usize },
+    /// An indexed parameter stored as a keccak256 hash can't be encoded back
+    /// into a log, as the original value is unknown.
+    #[error("indexed parameter `{name}` is hashed and can't be re-encoded")]
+    HashedTopic { name: String },
+    /// A field of the event can't be converted back into its Solidity type.
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
     /// The name of the event IS found in the ABI, yet decoding still failed.
     /// This might indicate an out-of-date ABI.
     #[error("could not decode, abi might mismatch data")]
-    DecodingError(#[from] alloy_dyn_abi::Error),
+    // Without `std`, alloy's and serde_json's errors don't implement
+    // `core::error::Error` and so can't be exposed as the source.
+    DecodingError(#[cfg_attr(feature = "std", from)] alloy_dyn_abi::Error),
+    /// The log, revert data or calldata could not be decoded as the event,
+    /// error or function with the given signature. alloy doesn't report which
+    /// parameter failed, so the underlying error is kept as the source.
+    ///
+    /// With the `capture-input-on-error` feature the error also holds the
+    /// offending input as `input`, e.g. `{"topics": [...], "data": "0x..."}`
+    /// for logs, so that it can be logged without the original source.
+    #[error("failed decoding {signature}: {error}{}", captured_input(.input))]
+    DecodingFailed {
+        signature: String,
+        #[cfg_attr(feature = "std", source)]
+        error: alloy_dyn_abi::Error,
+        /// The topics, for logs, and data which failed to decode, as `0x`-hex.
+        /// Always `None` without the `capture-input-on-error` feature.
+        input: Option<Box<Value>>,
+    },
+    /// Every parameter of the event is indexed, yet the log carries data.
+    /// This strongly suggests the log was emitted by a different event with
+    /// the same signature, e.g. an ERC721 `Transfer` read with an ERC20 ABI.
+    #[error("{signature} has no data parameters, but the log carries {len} data bytes")]
+    UnexpectedData { signature: String, len: usize },
+    /// Several events share the selector (or requested name) of the log, and
+    /// none of them could decode it. Holds the error of the last candidate.
+    #[error("none of the {tried} candidate events could decode the log")]
+    AmbiguousOrFailed {
+        tried: usize,
+        #[source]
+        last: Box<ParsingError>,
+    },
+    /// The 4-byte selector of the revert data is neither found in the ABI nor
+    /// one of the builtin `Error(string)` and `Panic(uint256)` errors.
+    #[error("error not found for given abi")]
+    UnknownError { selector: Selector },
+    /// The 4-byte selector of the calldata is not found in the ABI.
+    #[error("function not found for given abi")]
+    UnknownFunction { selector: Selector },
+    /// The decoded data could not be deserialized into the requested type.
+    #[error("could not deserialize decoded data")]
+    Deserialization(#[cfg_attr(feature = "std", source)] serde_json::Error),
+    /// A decoded value nests arrays, tuples and structs deeper than
+    /// [`OutputOptions::max_depth`].
+    #[error("decoded value is nested {depth} levels deep, more than the limit of {max_depth}")]
+    DepthExceeded { depth: usize, max_depth: usize },
+    /// A decoded event could not be serialized.
+    #[error("could not serialize decoded event")]
+    Serialization(#[cfg_attr(feature = "std", source)] serde_json::Error),
+    /// The parameter at `index` has no name in the ABI, and
+    /// [`UnnamedParameters::Reject`] is set.
+    #[error("parameter {index} has no name")]
+    UnnamedParameter { index: usize },
+    /// An event signature handed to [`Parser::from_signatures`] couldn't be
+    /// parsed.
+    #[error("invalid event signature `{signature}`")]
+    InvalidSignature {
+        signature: String,
+        #[cfg_attr(feature = "std", source)]
+        error: alloy_json_abi::parser::Error,
+    },
+    /// Events passed to [`Parser::require_events`] are not in the ABI.
+    #[error("no events named {} in the abi", names.join(", "))]
+    MissingEvents { names: Vec<String> },
+    /// Fewer than 4 bytes were supplied, so no selector could be read.
+    #[error("calldata of {len} bytes is too short to contain a selector")]
+    ShortCalldata { len: usize },
+}
+
+#[cfg(not(feature = "std"))]
+impl From<alloy_dyn_abi::Error> for ParsingError {
+    fn from(err: alloy_dyn_abi::Error) -> Self {
+        ParsingError::DecodingError(err)
+    }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(abi: &'a JsonAbi) -> Self {
-        Self { abi }
+        Self::with_options(abi, OutputOptions::default())
     }
 
-    pub fn parse(&self, log: &Log) -> Result<KeyedEvent, ParsingError> {
-        let selector = log.topics.first().unwrap();
-        let definition = self
-            .abi
-            .events()
-            .find(|e| e.selector().0 == selector.0)
-            .ok_or(ParsingError::UnknownEvent {
-                selector: *selector,
-            })?;
+    /// Starts configuring a parser option by option.
+    pub fn builder() -> ParserBuilder {
+        ParserBuilder::new()
+    }
 
-        let topics = log.topics.iter().map(|t| B256::from_slice(&t.0));
-        let decoded = definition
-            .decode_log_parts(topics, &log.data, true)
-            .map_err(ParsingError::DecodingError)?;
-        let indexed = definition.inputs.iter().filter(|e| e.indexed);
-        let body = definition.inputs.iter().filter(|e| !e.indexed);
+    /// Creates a parser which converts decoded values according to `options`.
+    pub fn with_options(abi: &'a JsonAbi, options: OutputOptions) -> Self {
+        Self::from_abis_with_options(core::slice::from_ref(abi), options)
+    }
 
-        let indexed = indexed.zip(decoded.indexed);
-        let body = body.zip(decoded.body);
+    /// Creates a parser recognizing the events, errors and functions of all
+    /// `abis`, e.g. those of a router, a factory and its pairs.
+    ///
+    /// Events sharing a selector are tried in the order of `abis`, and the
+    /// first to decode the log cleanly wins. Listing the implementation ABIs
+    /// of a proxy newest first thus prefers the newest matching layout; see
+    /// [`Parser::parse_with_abi_index`].
+    pub fn from_abis(abis: &'a [JsonAbi]) -> Self {
+        Self::from_abis_with_options(abis, OutputOptions::default())
+    }
 
-        let values: Map<String, Value> = indexed
-            .chain(body)
-            .map(|(k, v)| {
-                (k.name.clone(), dyn_sol_to_json(v))
-            })
+    /// Parses a JSON ABI and builds a parser owning it. The human-readable
+    /// form of [`Parser::from_abi_items`] is accepted too, as a JSON array of
+    /// strings, and tried only if `abi_json` isn't a JSON ABI.
+    pub fn from_json_str(abi_json: &str) -> Result<OwnedParser, serde_json::Error> {
+        let err = match serde_json::from_str(abi_json) {
+            Ok(abi) => return Ok(OwnedParser::new(abi)),
+            Err(err) => err,
+        };
+        let Ok(items) = serde_json::from_str::<Vec<String>>(abi_json) else {
+            return Err(err);
+        };
+        let abi =
+            JsonAbi::parse(items.iter().map(String::as_str)).map_err(serde::de::Error::custom)?;
+        Ok(OwnedParser::new(abi))
+    }
+
+    /// Builds a parser owning the ABI held in an already parsed JSON value.
+    pub fn from_json_value(abi: Value) -> Result<OwnedParser, serde_json::Error> {
+        // `JsonAbi` borrows strings while deserializing, which only a
+        // borrowed `Value` can provide.
+        Ok(OwnedParser::new(JsonAbi::deserialize(&abi)?))
+    }
+
+    /// Parses a human-readable ABI, such as
+    /// `["event Transfer(address indexed from, address indexed to, uint256 value)"]`,
+    /// and builds a parser owning it.
+    pub fn from_abi_items<'s>(
+        items: impl IntoIterator<Item = &'s str>,
+    ) -> Result<OwnedParser, alloy_json_abi::parser::Error> {
+        Ok(OwnedParser::new(JsonAbi::parse(items)?))
+    }
+
+    /// Builds a parser owning only the events described by `signatures`,
+    /// such as `Transfer(address indexed from, address indexed to, uint256 value)`.
+    /// The `event` keyword is optional.
+    pub fn from_signatures(signatures: &[&str]) -> Result<OwnedParser, ParsingError> {
+        let mut abi = JsonAbi::new();
+        for signature in signatures {
+            let event =
+                Event::parse(signature).map_err(|error| ParsingError::InvalidSignature {
+                    signature: signature.to_string(),
+                    error,
+                })?;
+            abi.events
+                .entry(event.name.clone())
+                .or_default()
+                .push(event);
+        }
+        Ok(OwnedParser::new(abi))
+    }
+
+    /// Like [`Parser::from_abis`], converting decoded values according to
+    /// `options`.
+    pub fn from_abis_with_options(abis: &'a [JsonAbi], options: OutputOptions) -> Self {
+        let mut events: HashMap<_, Vec<_>> = HashMap::new();
+        let mut anonymous = Vec::new();
+        let mut names: HashMap<_, Vec<_>> = HashMap::new();
+        let mut errors = HashMap::new();
+        let mut functions = HashMap::new();
+        for abi in abis {
+            for event in abi.events() {
+                names.entry(event.name.clone()).or_default().push(event);
+                if event.anonymous {
+                    anonymous.push(event);
+                    continue;
+                }
+                let candidates = events.entry(event.selector()).or_default();
+                if !candidates.contains(&event) {
+                    candidates.push(event);
+                }
+            }
+            for error in abi.errors() {
+                errors.entry(error.selector()).or_insert(error);
+            }
+            for function in abi.functions() {
+                functions.entry(function.selector()).or_insert(function);
+            }
+        }
+        Self {
+            abis,
+            events,
+            anonymous,
+            names,
+            errors,
+            functions,
+            options,
+        }
+    }
+
+    /// The ABIs this parser decodes against.
+    pub fn abis(&self) -> &'a [JsonAbi] {
+        self.abis
+    }
+
+    /// Checks that the ABI has an event called each of `names`, e.g. at
+    /// startup to catch a stale ABI or configuration before the first log
+    /// fails to decode. Every missing name is reported at once.
+    pub fn require_events(&self, names: &[&str]) -> Result<(), ParsingError> {
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| !self.names.contains_key(**name))
+            .map(|name| name.to_string())
             .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(ParsingError::MissingEvents { names: missing })
+        }
+    }
 
-        Ok(KeyedEvent {
-            name: definition.name.clone(),
-            data: Value::Object(values),
+    /// The topic0 of the event called `name`, e.g. to build an `eth_getLogs`
+    /// filter for it. For overloaded events this is the first non-anonymous
+    /// overload in ABI order; see [`Parser::selectors_of`].
+    pub fn selector_of(&self, name: &str) -> Option<B256> {
+        self.selectors_of(name).first().copied()
+    }
+
+    /// The topic0 of every overload of the event called `name`, in ABI
+    /// order. Anonymous overloads are left out, as their logs carry no
+    /// topic0 to filter on. Empty if there is no such event.
+    pub fn selectors_of(&self, name: &str) -> Vec<B256> {
+        let overloads = self.names.get(name).into_iter().flatten();
+        overloads
+            .filter(|event| !event.anonymous)
+            .map(|event| event.selector())
+            .collect()
+    }
+
+    /// The name and topic0 of every non-anonymous event this parser decodes,
+    /// sorted by name. Useful to filter `eth_getLogs` requests down to the
+    /// logs the parser understands.
+    pub fn event_selectors(&self) -> Vec<(String, B256)> {
+        let mut selectors: Vec<_> = self
+            .events
+            .iter()
+            .flat_map(|(selector, events)| events.iter().map(|e| (e.name.clone(), *selector)))
+            .collect();
+        selectors.sort();
+        selectors
+    }
+
+    /// Counts the definitions the parser can decode, e.g. to check that an
+    /// ABI loaded completely. Definitions shared by several ABIs are counted
+    /// once.
+    pub fn stats(&self) -> ParserStats {
+        ParserStats {
+            events: self.events.values().map(Vec::len).sum(),
+            errors: self.errors.len(),
+            functions: self.functions.len(),
+            anonymous_events: self.anonymous.len(),
+        }
+    }
+
+    pub fn parse(&self, log: &impl EventLog) -> Result<KeyedEvent, ParsingError> {
+        let topics = log.topics();
+        match self.decode_with_topics(&topics, log) {
+            Ok((definition, decoded)) => self.to_keyed(definition, decoded),
+            Err(err) if self.passes_through(&err) => Ok(unknown_event(&topics, log.data())),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Like [`Parser::parse`], but borrows the event's name from the ABI
+    /// instead of allocating it, and leaves out every other field.
+    pub fn parse_ref(&self, log: &impl EventLog) -> Result<KeyedEventRef<'a>, ParsingError> {
+        let (definition, decoded) = self.decode_log(log)?;
+        self.check_names(definition.inputs.iter().map(|e| &e.name))?;
+        self.check_depth(decoded.event.indexed.iter().chain(&decoded.event.body))?;
+        Ok(KeyedEventRef {
+            name: &definition.name,
+            data: self.event_data(definition, decoded.event, Map::new()),
         })
     }
-}
 
-pub fn dyn_sol_to_json(val: DynSolValue) -> Value {
-    use base64::prelude::*;
-
-    match val {
-        DynSolValue::Bool(b) => Value::Bool(b),
-        DynSolValue::Int(i, _) => Value::String(i.to_dec_string()),
-        DynSolValue::Uint(i, _) => Value::String(i.to_string()),
-        DynSolValue::FixedBytes(v, _) => Value::String(BASE64_STANDARD.encode(v.0)),
-        DynSolValue::Address(a) => Value::String(a.to_string()),
-        DynSolValue::Function(p) => Value::String(p.to_string()),
-        DynSolValue::Bytes(b) => Value::String(BASE64_STANDARD.encode(b)),
-        DynSolValue::String(s) => Value::String(s),
-        DynSolValue::Array(a) => Value::Array(a.into_iter().map(dyn_sol_to_json).collect()),
-        DynSolValue::FixedArray(a) => Value::Array(a.into_iter().map(dyn_sol_to_json).collect()),
-        DynSolValue::Tuple(a) => Value::Array(a.into_iter().map(dyn_sol_to_json).collect()),
-        DynSolValue::CustomStruct {
-            name: _,
-            prop_names,
-            tuple,
-        } => {
-            let map = prop_names
-                .into_iter()
-                .zip(tuple.into_iter().map(dyn_sol_to_json))
-                .collect();
-            Value::Object(map)
+    /// Like [`Parser::parse`], but decodes into `out`, overwriting every field
+    /// it held before, including `meta`. Reusing one buffer across many logs
+    /// saves the per-event allocation of the name, but not of the data map,
+    /// whose entries are freed when it is cleared. On error `out` is left as
+    /// it was.
+    pub fn parse_into(
+        &self,
+        log: &impl EventLog,
+        out: &mut KeyedEvent,
+    ) -> Result<(), ParsingError> {
+        let topics = log.topics();
+        match self.decode_with_topics(&topics, log) {
+            Ok((definition, decoded)) => self.fill_keyed(definition, decoded, out),
+            Err(err) if self.passes_through(&err) => {
+                *out = unknown_event(&topics, log.data());
+                Ok(())
+            }
+            Err(err) => Err(err),
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde::Deserialize;
+    /// Decodes a log into scalar columns keyed by their path, such as
+    /// `packet.timeout.0`, for columnar formats like Parquet. Parameters and
+    /// struct members come in ABI order and array elements by index, so an
+    /// event always yields its columns in the same order. Parts of the path
+    /// are joined with [`OutputOptions::separator`]; `split_indexed`,
+    /// `flatten` and `sorted_keys` don't apply. Empty arrays are kept as a
+    /// single column.
+    pub fn parse_columns(&self, log: &impl EventLog) -> Result<Vec<(String, Value)>, ParsingError> {
+        let (definition, decoded) = self.decode_log(log)?;
+        self.check_names(definition.inputs.iter().map(|e| &e.name))?;
+        self.check_depth(decoded.event.indexed.iter().chain(&decoded.event.body))?;
+        let inputs = definition.inputs.iter().enumerate();
+        let indexed = inputs.clone().filter(|(_, e)| e.indexed);
+        let body = inputs.filter(|(_, e)| !e.indexed);
+        let mut values: Vec<_> = indexed
+            .zip(decoded.event.indexed)
+            .chain(body.zip(decoded.event.body))
+            .collect();
+        values.sort_by_key(|((i, _), _)| *i);
 
-    fn logs() -> Vec<Log> {
-        #[derive(Deserialize)]
-        struct ApiResponse {
-            pub result: Vec<Log>,
+        let mut columns = Vec::new();
+        for ((i, input), value) in values {
+            let key = param_key(&input.name, i, self.options.key_case);
+            let ty = input.resolve()?;
+            let value = self.input_json(input, value);
+            columns_into(
+                key,
+                value,
+                Some(&ty),
+                &input.components,
+                &self.options,
+                &mut columns,
+            );
         }
-        let file = include_str!("../testdata/logs.json");
-        let response: ApiResponse = serde_json::from_str(&file).unwrap();
-        response.result
+        Ok(columns)
     }
 
-    fn erc20_abi() -> JsonAbi {
-        let json = include_str!("../testdata/erc20.json");
-        serde_json::from_str(&json).unwrap()
+    /// Decodes a raw log object in the JSON-RPC shape returned by
+    /// `eth_getLogs`, reading the `0x`-hex `topics`, `data` and, if present,
+    /// `address` fields.
+    pub fn parse_rpc(&self, value: &Value) -> Result<KeyedEvent, ParsingError> {
+        let log = RpcLog::deserialize(value).map_err(ParsingError::Deserialization)?;
+        self.parse(&log)
     }
 
-    #[test]
-    fn erc20_parsing_works() {
-        let abi = erc20_abi();
-        let parser = Parser::new(&abi);
-        for log in logs() {
-            parser.parse(&log).unwrap();
+    /// Like [`Parser::parse`], but also returns the position in `abis` of the
+    /// ABI whose event decoded the log. When several ABIs hold an identical
+    /// event, the first of them is reported.
+    pub fn parse_with_abi_index(
+        &self,
+        log: &impl EventLog,
+    ) -> Result<(KeyedEvent, usize), ParsingError> {
+        let (event, definition) = self.parse_with_def(log)?;
+        let index = self
+            .abis
+            .iter()
+            .position(|abi| abi.events().any(|e| core::ptr::eq(e, definition)))
+            .expect("definitions are borrowed from the parser's abis");
+        Ok((event, index))
+    }
+
+    /// Like [`Parser::parse`], but also returns the ABI definition of the
+    /// matched event, e.g. to read the parameter types.
+    pub fn parse_with_def(
+        &self,
+        log: &impl EventLog,
+    ) -> Result<(KeyedEvent, &'a Event), ParsingError> {
+        let (definition, decoded) = self.decode_log(log)?;
+        Ok((self.to_keyed(definition, decoded)?, definition))
+    }
+
+    /// Like [`Parser::parse`], but returns `log` alongside its decoded form,
+    /// so results can't drift out of step with their inputs.
+    pub fn parse_paired<'l, L: EventLog>(
+        &self,
+        log: &'l L,
+    ) -> Result<(KeyedEvent, &'l L), ParsingError> {
+        Ok((self.parse(log)?, log))
+    }
+
+    /// Decodes an event without an ABI, given the types of its parameters in
+    /// declaration order and which of them are indexed. Indexed values are
+    /// read from `topics` in order, so leave out the topic0 of non-anonymous
+    /// events. Values are returned in declaration order; indexed parameters of
+    /// dynamic types hold their hash. Fails with
+    /// [`EncodeLengthMismatch`](alloy_dyn_abi::Error::EncodeLengthMismatch)
+    /// if `types` and `indexed` differ in length.
+    pub fn try_decode_raw(
+        topics: &[B256],
+        data: &[u8],
+        types: &[DynSolType],
+        indexed: &[bool],
+    ) -> Result<Vec<DynSolValue>, alloy_dyn_abi::Error> {
+        if types.len() != indexed.len() {
+            return Err(alloy_dyn_abi::Error::EncodeLengthMismatch {
+                expected: indexed.len(),
+                actual: types.len(),
+            });
         }
+        let (topic_types, body_types): (Vec<_>, Vec<_>) = types
+            .iter()
+            .zip(indexed)
+            .partition(|(_, indexed)| **indexed);
+        let event = DynSolEvent::new_unchecked(
+            None,
+            topic_types.into_iter().map(|(ty, _)| ty.clone()).collect(),
+            DynSolType::Tuple(body_types.into_iter().map(|(ty, _)| ty.clone()).collect()),
+        );
+        let decoded = event.decode_log_parts(topics.iter().copied(), data, true)?;
+
+        let mut topic_values = decoded.indexed.into_iter();
+        let mut body_values = decoded.body.into_iter();
+        Ok(indexed
+            .iter()
+            .filter_map(|indexed| {
+                if *indexed {
+                    topic_values.next()
+                } else {
+                    body_values.next()
+                }
+            })
+            .collect())
     }
 
-    mod ibc {
-        use super::*;
+    /// Like [`Parser::parse`], but returns `Ok(None)` for logs of events which
+    /// are not in the ABI, separating "not my event" from "my event, but
+    /// corrupt data".
+    pub fn parse_opt(&self, log: &impl EventLog) -> Result<Option<KeyedEvent>, ParsingError> {
+        match self.parse(log) {
+            Ok(event) => Ok(Some(event)),
+            Err(ParsingError::UnknownEvent { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
 
-        fn logs() -> Vec<Log> {
-            let file = include_str!("../testdata/ibc/logs.json");
-            let response: Vec<Log> = serde_json::from_str(&file).unwrap();
-            response
+    /// Decodes a log without converting the values to JSON, leaving numeric
+    /// and byte handling to the caller.
+    pub fn decode(&self, log: &impl EventLog) -> Result<DecodedEvent, ParsingError> {
+        let (definition, decoded) = self.decode_log(log)?;
+        Ok(DecodedEvent::new(definition, decoded.event))
+    }
+
+    /// Finds the events matching the log's topic0 and decodes it against them.
+    fn decode_log(&self, log: &impl EventLog) -> Result<(&'a Event, Decoded), ParsingError> {
+        self.decode_with_topics(&log.topics(), log)
+    }
+
+    /// [`Parser::decode_log`] with the topics of `log` already fetched, as
+    /// converting them may allocate, e.g. for ethers logs.
+    fn decode_with_topics(
+        &self,
+        topics: &[B256],
+        log: &impl EventLog,
+    ) -> Result<(&'a Event, Decoded), ParsingError> {
+        let selector = topics.first().ok_or(ParsingError::MissingTopic0 {
+            address: log.address(),
+        })?;
+        let candidates = self.events.get(selector).into_iter().flatten();
+        let unknown = ParsingError::UnknownEvent {
+            selector: *selector,
+        };
+        let (definition, mut decoded) =
+            self.decode_first(candidates.copied(), topics, log.data(), unknown)?;
+        decoded.selector = Some(*selector);
+        Ok((definition, decoded))
+    }
+
+    /// Describes, for humans, why `log` does or doesn't decode: for every
+    /// event with its topic0, the expected topics and data against those of
+    /// the log, and the outcome of decoding it with the configured options.
+    /// Best effort, meant for diagnosing stale ABIs rather than for parsing.
+    pub fn explain_failure(&self, log: &impl EventLog) -> String {
+        let topics = log.topics();
+        let data = log.data();
+        let Some(selector) = topics.first() else {
+            return format!(
+                "the log has no topics, so it can only be an anonymous event without \
+                 indexed parameters; the abi has {} anonymous events",
+                self.anonymous.len()
+            );
+        };
+        let Some(candidates) = self.events.get(selector) else {
+            return format!("topic0 {selector} matches no event in the abi");
+        };
+
+        let mut lines = Vec::new();
+        for definition in candidates {
+            let (indexed, body): (Vec<_>, Vec<_>) =
+                definition.inputs.iter().partition(|input| input.indexed);
+            let types = |params: &[&EventParam]| {
+                let types: Vec<_> = params.iter().map(|p| p.selector_type()).collect();
+                types.join(", ")
+            };
+            lines.push(format!("{}:", definition.signature()));
+
+            let expected = indexed.len() + 1;
+            let verdict = if expected == topics.len() {
+                "ok"
+            } else {
+                "mismatch"
+            };
+            lines.push(format!(
+                "  topics: expected {expected} (topic0 and {} indexed: {}), found {}: {verdict}",
+                indexed.len(),
+                types(&indexed),
+                topics.len(),
+            ));
+
+            let body_types = body
+                .iter()
+                .map(|p| p.resolve())
+                .collect::<Result<Vec<_>, _>>();
+            match body_types {
+                Ok(body_types) => {
+                    let minimum = DynSolType::Tuple(body_types).minimum_words() * 32;
+                    let verdict = match data.len() {
+                        len if len < minimum => "too short",
+                        len if !len.is_multiple_of(32) => "not a whole number of words",
+                        _ => "ok",
+                    };
+                    lines.push(format!(
+                        "  data: expected at least {minimum} bytes ({}), found {}: {verdict}",
+                        types(&body),
+                        data.len(),
+                    ));
+                }
+                Err(err) => lines.push(format!("  data: the abi types don't resolve: {err}")),
+            }
+
+            match self.decode_as(definition, &topics, data) {
+                Ok(_) => lines.push("  decodes with the configured options".to_string()),
+                Err(err) => lines.push(format!("  fails to decode: {err}")),
+            }
         }
+        lines.join("\n")
+    }
 
-        fn abi() -> JsonAbi {
-            let json = include_str!("../testdata/ibc/abi.json");
-            serde_json::from_str(&json).unwrap()
+    /// A JSON Schema of the `data` object [`Parser::parse`] produces for the
+    /// event called `name`, reflecting the configured [`OutputOptions`], e.g.
+    /// whether integers are strings or numbers. Overloaded events are
+    /// described with `oneOf`.
+    pub fn event_schema(&self, name: &str) -> Result<Value, ParsingError> {
+        let overloads = self
+            .names
+            .get(name)
+            .ok_or_else(|| ParsingError::UnknownEventName {
+                name: name.to_string(),
+            })?;
+        let mut schemas = overloads
+            .iter()
+            .map(|definition| schema::event_schema(definition, &self.options))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut schema = if schemas.len() == 1 {
+            schemas.remove(0)
+        } else {
+            serde_json::json!({ "oneOf": schemas })
+        };
+        schema["$schema"] = "https://json-schema.org/draft/2020-12/schema".into();
+        Ok(schema)
+    }
+
+    /// Decodes only the indexed parameters of a log from its topics, leaving
+    /// the data untouched, e.g. to cheaply filter transfers by `from` or `to`.
+    /// Indexed `string`, `bytes`, arrays and structs are hashes, as in
+    /// [`Parser::parse`].
+    pub fn decode_indexed(&self, log: &impl EventLog) -> Result<Map<String, Value>, ParsingError> {
+        let topics = log.topics();
+        let selector = topics.first().ok_or(ParsingError::MissingTopic0 {
+            address: log.address(),
+        })?;
+        let mut last = ParsingError::UnknownEvent {
+            selector: *selector,
+        };
+        for definition in self.events.get(selector).into_iter().flatten() {
+            match self.decode_topics(definition, &topics) {
+                Ok(indexed) => return Ok(indexed),
+                Err(err) => last = err,
+            }
+        }
+        Err(last)
+    }
+
+    fn decode_topics(
+        &self,
+        definition: &Event,
+        topics: &[B256],
+    ) -> Result<Map<String, Value>, ParsingError> {
+        check_topic_count(definition, topics)?;
+        self.check_names(definition.inputs.iter().map(|e| &e.name))?;
+        let indexed = definition
+            .inputs
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.indexed)
+            .zip(&topics[!definition.anonymous as usize..]);
+        let mut map = Map::new();
+        for ((i, input), topic) in indexed {
+            let value = if is_hashed(input) {
+                hashed_json(input, *topic)
+            } else {
+                let value = input.resolve()?.abi_decode(topic.as_slice())?;
+                field_to_json(&input.name, value, &input.components, &self.options)
+            };
+            map.insert(param_key(&input.name, i, self.options.key_case), value);
+        }
+        Ok(finish_map(map, &self.options))
+    }
+
+    /// Decodes a log and deserializes its data into `T`.
+    ///
+    /// The data is the same object [`Parser::parse`] produces, so the field
+    /// names of `T` must match the ABI input names (use `#[serde(rename)]`
+    /// where they differ), and values follow the configured
+    /// [`OutputOptions`]: by default integers and addresses are strings.
+    pub fn parse_typed<T: DeserializeOwned>(&self, log: &impl EventLog) -> Result<T, ParsingError> {
+        let event = self.parse(log)?;
+        serde_json::from_value(event.data).map_err(ParsingError::Deserialization)
+    }
+
+    /// Decodes a log and serializes the event to compact JSON, e.g. for
+    /// logging.
+    pub fn parse_to_string(&self, log: &impl EventLog) -> Result<String, ParsingError> {
+        serde_json::to_string(&self.parse(log)?).map_err(ParsingError::Serialization)
+    }
+
+    /// Like [`Parser::parse_to_string`], but pretty-printed over several
+    /// lines.
+    pub fn parse_to_string_pretty(&self, log: &impl EventLog) -> Result<String, ParsingError> {
+        serde_json::to_string_pretty(&self.parse(log)?).map_err(ParsingError::Serialization)
+    }
+
+    /// Decodes every log independently, so a failure doesn't affect the
+    /// outcome of the others.
+    pub fn parse_all<L: EventLog>(&self, logs: &[L]) -> Vec<Result<KeyedEvent, ParsingError>> {
+        logs.iter().map(|log| self.parse(log)).collect()
+    }
+
+    /// Lazily decodes each log as the iterator is advanced, so a stream of
+    /// logs can be filtered or truncated without decoding all of it.
+    /// Accepts owned logs as well as references.
+    pub fn parse_iter<'s, I>(
+        &'s self,
+        logs: I,
+    ) -> impl Iterator<Item = Result<KeyedEvent, ParsingError>> + 's
+    where
+        I: IntoIterator,
+        I::Item: EventLog,
+        I::IntoIter: 's,
+    {
+        logs.into_iter().map(move |log| self.parse(&log))
+    }
+
+    /// [`Parser::parse_iter`] for a stream of logs, e.g. a live subscription,
+    /// decoding each log as it arrives.
+    #[cfg(feature = "stream")]
+    pub fn parse_stream<'s, S>(
+        &'s self,
+        logs: S,
+    ) -> impl futures::Stream<Item = Result<KeyedEvent, ParsingError>> + 's
+    where
+        S: futures::Stream + 's,
+        S::Item: EventLog,
+    {
+        use futures::StreamExt;
+        logs.map(move |log| self.parse(&log))
+    }
+
+    /// Decodes every log in parallel on the rayon thread pool. Results are in
+    /// the same order as `logs`, exactly as [`Parser::parse_all`] returns them.
+    #[cfg(feature = "rayon")]
+    pub fn par_parse_all<L: EventLog + Sync>(
+        &self,
+        logs: &[L],
+    ) -> Vec<Result<KeyedEvent, ParsingError>> {
+        use rayon::prelude::*;
+
+        logs.par_iter().map(|log| self.parse(log)).collect()
+    }
+
+    /// Decodes every log and buckets the events by name. Logs which fail to
+    /// decode, including those of unknown events, are only counted, in the
+    /// second element.
+    pub fn parse_grouped<L: EventLog>(
+        &self,
+        logs: &[L],
+    ) -> (BTreeMap<String, Vec<KeyedEvent>>, usize) {
+        let mut grouped: BTreeMap<String, Vec<KeyedEvent>> = BTreeMap::new();
+        let mut skipped = 0;
+        for result in logs.iter().map(|log| self.parse(log)) {
+            match result {
+                Ok(event) => grouped.entry(event.name.clone()).or_default().push(event),
+                Err(_) => skipped += 1,
+            }
+        }
+        (grouped, skipped)
+    }
+
+    /// Summarizes the logs of a transaction as one JSON object, holding the
+    /// `data` of the events under their name in the order of `logs`, e.g.
+    /// `{"Transfer": [{...}, {...}], "Approval": [{...}]}`. Logs which fail
+    /// to decode, including those of unknown events, are kept under
+    /// `unknown` with their raw `topic0`, `topics` and `data` as hex.
+    pub fn parse_tx_summary<L: EventLog>(&self, logs: &[L]) -> Value {
+        let mut summary = Map::new();
+        for log in logs {
+            let (name, data) = match self.parse(log) {
+                Ok(event) => (event.name, event.data),
+                Err(_) => {
+                    let unknown = unknown_event(&log.topics(), log.data());
+                    ("unknown".to_string(), unknown.data)
+                }
+            };
+            let entries = summary
+                .entry(name)
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(entries) = entries {
+                entries.push(data);
+            }
+        }
+        Value::Object(summary)
+    }
+
+    /// Whether topic0 of the log is the selector of an event in the ABI.
+    /// Nothing is decoded, so [`Parser::parse`] may still fail on the log.
+    /// Logs without topics, including those of anonymous events, are never
+    /// parseable by selector.
+    pub fn can_parse(&self, log: &impl EventLog) -> bool {
+        log.topics()
+            .first()
+            .is_some_and(|selector| self.events.contains_key(selector))
+    }
+
+    /// Tallies the topic0 of every log against the selectors in the ABI
+    /// without decoding anything. Logs without topics or with an unknown
+    /// topic0, which includes those of anonymous events, are only counted, in
+    /// the second element. Far cheaper than [`Parser::parse_all`] when only a
+    /// histogram is needed, but a log is counted even if it wouldn't decode.
+    pub fn count_by_selector<L: EventLog>(&self, logs: &[L]) -> (BTreeMap<B256, usize>, usize) {
+        let mut counts: BTreeMap<B256, usize> = BTreeMap::new();
+        let mut unknown = 0;
+        for log in logs {
+            match log.topics().first() {
+                Some(selector) if self.events.contains_key(selector) => {
+                    *counts.entry(*selector).or_default() += 1
+                }
+                _ => unknown += 1,
+            }
+        }
+        (counts, unknown)
+    }
+
+    /// Decodes every log of a transaction receipt, recording the log's
+    /// provenance in `meta`. The transaction hash and block number of the
+    /// receipt are used where the log itself lacks them.
+    #[cfg(feature = "ethers")]
+    pub fn parse_receipt(
+        &self,
+        receipt: &ethers::core::types::TransactionReceipt,
+    ) -> Vec<Result<KeyedEvent, ParsingError>> {
+        receipt
+            .logs
+            .iter()
+            .map(|log| {
+                let mut event = self.parse_with_meta(log)?;
+                if let Some(meta) = event.meta.as_mut() {
+                    meta.transaction_hash
+                        .get_or_insert(B256::from(receipt.transaction_hash.0));
+                    if let Some(block) = receipt.block_number {
+                        meta.block_number.get_or_insert(block.as_u64());
+                    }
+                }
+                Ok(event)
+            })
+            .collect()
+    }
+
+    /// Decodes every log, dropping those of events not in the ABI. Any other
+    /// failure is returned alongside the decoded events rather than aborting
+    /// the batch.
+    pub fn parse_known<L: EventLog>(&self, logs: &[L]) -> (Vec<KeyedEvent>, Vec<ParsingError>) {
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
+        for result in logs.iter().map(|log| self.parse(log)) {
+            match result {
+                Ok(event) => events.push(event),
+                Err(ParsingError::UnknownEvent { .. }) => {}
+                Err(err) => errors.push(err),
+            }
+        }
+        (events, errors)
+    }
+
+    /// Decodes a log against the anonymous events of the ABI. As these have no
+    /// selector, every anonymous event with a matching number of topics is
+    /// tried in ABI order and the first which decodes is returned.
+    pub fn parse_anonymous(&self, log: &impl EventLog) -> Result<KeyedEvent, ParsingError> {
+        let topics = log.topics();
+        let (definition, decoded) = self
+            .anonymous
+            .iter()
+            .filter(|e| e.num_topics() == topics.len())
+            .find_map(|e| Some((e, self.decode_as(e, &topics, log.data()).ok()?)))
+            .ok_or(ParsingError::UnknownAnonymousEvent)?;
+        self.to_keyed(definition, decoded)
+    }
+
+    /// Decodes a log as the event called `name`, skipping selector matching.
+    /// Overloads are tried in ABI order and the first successful decode is
+    /// returned; if all of them fail, the error of the last one is.
+    pub fn parse_as(&self, name: &str, log: &impl EventLog) -> Result<KeyedEvent, ParsingError> {
+        let overloads = self.names.get(name).into_iter().flatten().copied();
+        let unknown = ParsingError::UnknownEventName {
+            name: name.to_string(),
+        };
+        let topics = log.topics();
+        let (definition, decoded) = self.decode_first(overloads, &topics, log.data(), unknown)?;
+        self.to_keyed(definition, decoded)
+    }
+
+    /// Tries each candidate definition in order, returning the first
+    /// successful decode. A single failing candidate returns its own error,
+    /// several return [`ParsingError::AmbiguousOrFailed`]. `unknown` is
+    /// returned if there are no candidates at all.
+    fn decode_first<'e>(
+        &self,
+        candidates: impl IntoIterator<Item = &'e Event>,
+        topics: &[B256],
+        data: &[u8],
+        unknown: ParsingError,
+    ) -> Result<(&'e Event, Decoded), ParsingError> {
+        let mut last = unknown;
+        let mut tried = 0;
+        for definition in candidates {
+            match self.decode_as(definition, topics, data) {
+                Ok(decoded) => return Ok((definition, decoded)),
+                Err(err) => last = err,
+            }
+            tried += 1;
+        }
+        if tried > 1 {
+            last = ParsingError::AmbiguousOrFailed {
+                tried,
+                last: Box::new(last),
+            };
+        }
+        Err(last)
+    }
+
+    fn decode_as(
+        &self,
+        definition: &Event,
+        all_topics: &[B256],
+        data: &[u8],
+    ) -> Result<Decoded, ParsingError> {
+        let mut topics = all_topics;
+        if self.options.lenient_data {
+            // Only padding beyond the indexed parameters is dropped: a zero
+            // topic in an expected slot is a real value, e.g. a mint's `from`.
+            let expected = definition.num_topics();
+            while topics.len() > expected && topics.last().is_some_and(B256::is_zero) {
+                topics = &topics[..topics.len() - 1];
+            }
+        }
+        let extra = match check_topic_count(definition, topics) {
+            Ok(()) => 0,
+            Err(ParsingError::TopicCountMismatch { expected, found })
+                if !self.options.strict && found > expected =>
+            {
+                found - expected
+            }
+            Err(err) => return Err(err),
+        };
+        let (topics, extra) = topics.split_at(topics.len() - extra);
+        let attempt = |data: &[u8], validate: bool| {
+            definition.decode_log_parts(topics.iter().copied(), data, validate)
+        };
+        let decoded = |event, padded, strict| Decoded {
+            event,
+            padded,
+            strict: strict && extra.is_empty(),
+            extra_topics: extra.to_vec(),
+            raw: self
+                .options
+                .include_raw_topics
+                .then(|| (all_topics.to_vec(), Bytes::copy_from_slice(data))),
+            selector: None,
+        };
+
+        if !data.is_empty() && definition.inputs.iter().all(|input| input.indexed) {
+            if self.options.strict {
+                return Err(ParsingError::UnexpectedData {
+                    signature: definition.signature(),
+                    len: data.len(),
+                });
+            }
+            if let Ok(event) = attempt(&[], true) {
+                return Ok(decoded(event, false, false));
+            }
+        }
+        let err = match attempt(data, true) {
+            Ok(event) => return Ok(decoded(event, false, true)),
+            Err(err) => err,
+        };
+        let padded = (self.options.lenient_data && !data.len().is_multiple_of(32)).then(|| {
+            let mut padded = data.to_vec();
+            padded.resize(data.len().next_multiple_of(32), 0);
+            padded
+        });
+        if let Some(Ok(event)) = padded.as_deref().map(|padded| attempt(padded, true)) {
+            return Ok(decoded(event, true, true));
+        }
+        if !self.options.strict {
+            if let Ok(event) = attempt(padded.as_deref().unwrap_or(data), false) {
+                return Ok(decoded(event, padded.is_some(), false));
+            }
+        }
+        Err(decoding_failed(
+            definition.signature(),
+            err,
+            Some(all_topics),
+            data,
+        ))
+    }
+
+    fn to_keyed(&self, definition: &Event, decoded: Decoded) -> Result<KeyedEvent, ParsingError> {
+        let mut out = KeyedEvent::default();
+        self.fill_keyed(definition, decoded, &mut out)?;
+        Ok(out)
+    }
+
+    /// Overwrites every field of `out`, reusing its name and top-level data
+    /// map where possible. `out` is untouched if an error is returned.
+    fn fill_keyed(
+        &self,
+        definition: &Event,
+        decoded: Decoded,
+        out: &mut KeyedEvent,
+    ) -> Result<(), ParsingError> {
+        let Decoded {
+            event: decoded,
+            padded,
+            strict,
+            extra_topics,
+            raw,
+            selector,
+        } = decoded;
+        self.check_names(definition.inputs.iter().map(|e| &e.name))?;
+        self.check_depth(decoded.indexed.iter().chain(&decoded.body))?;
+        let data = match core::mem::take(&mut out.data) {
+            Value::Object(mut map) => {
+                map.clear();
+                map
+            }
+            _ => Map::new(),
+        };
+        let data = self.event_data(definition, decoded, data);
+
+        out.name.clear();
+        out.name.push_str(&definition.name);
+        out.selector = selector.unwrap_or_else(|| definition.selector());
+        out.signature = self
+            .options
+            .include_signature
+            .then(|| definition.signature());
+        out.data = data;
+        out.types = self.options.include_types.then(|| {
+            let inputs = definition.inputs.iter().enumerate();
+            inputs
+                .map(|(i, input)| {
+                    (
+                        param_key(&input.name, i, self.options.key_case),
+                        input.selector_type().into(),
+                    )
+                })
+                .collect()
+        });
+        out.meta = None;
+        out.padded = padded;
+        out.non_strict = !strict;
+        out.extra_topics = extra_topics;
+        (out.raw_topics, out.raw_data) = raw.unzip();
+        Ok(())
+    }
+
+    /// Converts the decoded value of `input`, marking indexed values which
+    /// are only a hash as such.
+    fn input_json(&self, input: &EventParam, value: DynSolValue) -> Value {
+        match value {
+            DynSolValue::FixedBytes(hash, _) if input.indexed && is_hashed(input) => {
+                hashed_json(input, hash)
+            }
+            value => field_to_json(&input.name, value, &input.components, &self.options),
+        }
+    }
+
+    /// Keys the decoded values by parameter into `data`, which must be empty,
+    /// and applies the configured layout.
+    fn event_data(
+        &self,
+        definition: &Event,
+        decoded: DynDecodedEvent,
+        mut data: Map<String, Value>,
+    ) -> Value {
+        let inputs = definition.inputs.iter().enumerate();
+        let indexed = inputs.clone().filter(|(_, e)| e.indexed);
+        let body = inputs.filter(|(_, e)| !e.indexed);
+
+        let indexed = indexed.zip(decoded.indexed);
+        let body = body.zip(decoded.body);
+
+        let to_json = |((i, k), v): ((usize, &EventParam), DynSolValue)| {
+            (
+                param_key(&k.name, i, self.options.key_case),
+                self.input_json(k, v),
+            )
+        };
+        if self.options.etherscan_style {
+            let mut params: Vec<_> = indexed.chain(body).collect();
+            params.sort_by_key(|((i, _), _)| *i);
+            let params = params.into_iter().map(|((i, k), v)| {
+                serde_json::json!({
+                    "name": param_key(&k.name, i, self.options.key_case),
+                    "type": k.selector_type(),
+                    "value": self.input_json(k, v),
+                    "indexed": k.indexed,
+                })
+            });
+            data.insert("params".to_string(), Value::Array(params.collect()));
+            let data = Value::Object(data);
+            return if self.options.sorted_keys {
+                sort_keys(data)
+            } else {
+                data
+            };
+        }
+        if self.options.split_indexed {
+            let indexed: Map<String, Value> = indexed.map(to_json).collect();
+            let body: Map<String, Value> = body.map(to_json).collect();
+            data.insert("indexed".to_string(), Value::Object(indexed));
+            data.insert("body".to_string(), Value::Object(body));
+        } else {
+            data.extend(indexed.chain(body).map(to_json));
+        }
+        let data = if self.options.flatten {
+            flatten(data, self.options.separator)
+        } else {
+            data
+        };
+        if self.options.sorted_keys {
+            sort_keys(Value::Object(data))
+        } else {
+            Value::Object(data)
+        }
+    }
+
+    /// Whether `err` is turned into an `UnknownEvent` by
+    /// [`OutputOptions::passthrough_unknown`].
+    fn passes_through(&self, err: &ParsingError) -> bool {
+        self.options.passthrough_unknown
+            && matches!(
+                err,
+                ParsingError::UnknownEvent { .. } | ParsingError::MissingTopic0 { .. }
+            )
+    }
+
+    /// Fails if any of `values` is nested beyond [`OutputOptions::max_depth`].
+    fn check_depth<'v>(
+        &self,
+        values: impl IntoIterator<Item = &'v DynSolValue>,
+    ) -> Result<(), ParsingError> {
+        check_depth(values, self.options.max_depth)
+    }
+
+    /// Fails on the first empty name if [`UnnamedParameters::Reject`] is set.
+    fn check_names<'n>(&self, names: impl Iterator<Item = &'n String>) -> Result<(), ParsingError> {
+        if self.options.unnamed_parameters != UnnamedParameters::Reject {
+            return Ok(());
+        }
+        match names.enumerate().find(|(_, name)| name.is_empty()) {
+            Some((index, _)) => Err(ParsingError::UnnamedParameter { index }),
+            None => Ok(()),
+        }
+    }
+
+    /// Re-encodes a decoded event into the topics and data of a log, the
+    /// inverse of [`Parser::parse`]. The definition is looked up by name and
+    /// selector, and `event.data` must have the shape produced by this
+    /// parser's [`OutputOptions`].
+    ///
+    /// Indexed `string`, `bytes`, arrays and structs only survive decoding as
+    /// their hash, so events with such parameters return
+    /// [`ParsingError::HashedTopic`].
+    pub fn encode(&self, event: &KeyedEvent) -> Result<(Vec<B256>, Vec<u8>), ParsingError> {
+        let definition = self
+            .names
+            .get(&event.name)
+            .into_iter()
+            .flatten()
+            .find(|e| e.selector() == event.selector)
+            .ok_or_else(|| ParsingError::UnknownEventName {
+                name: event.name.clone(),
+            })?;
+
+        let value_of = |section: &str, index: usize, input: &EventParam| {
+            let key = param_key(&input.name, index, self.options.key_case);
+            let ty = input.resolve()?;
+            let key = match self.options.split_indexed {
+                true if self.options.flatten => {
+                    format!("{section}{}{key}", self.options.separator)
+                }
+                _ => key,
+            };
+            let value = match (self.options.flatten, self.options.split_indexed) {
+                (true, _) => event.data.as_object().and_then(|flat| {
+                    let value = json::unflatten(flat, &key, &ty, &input.components, &self.options);
+                    value.map(Cow::Owned)
+                }),
+                (false, true) => event.data[section].get(&key).map(Cow::Borrowed),
+                (false, false) => event.data.get(&key).map(Cow::Borrowed),
+            };
+            let value = value.ok_or_else(|| ConversionError::MissingField {
+                ty: event.name.clone(),
+                name: key,
+            })?;
+            Ok::<_, ParsingError>(json_to_field(
+                &input.name,
+                &value,
+                &ty,
+                &input.components,
+                &self.options,
+            )?)
+        };
+
+        let mut topics = Vec::with_capacity(definition.num_topics());
+        if !definition.anonymous {
+            topics.push(definition.selector());
+        }
+        let mut values = Vec::new();
+        for (index, input) in definition.inputs.iter().enumerate() {
+            if !input.indexed {
+                values.push(value_of("body", index, input)?);
+            } else if is_hashed(input) {
+                return Err(ParsingError::HashedTopic {
+                    name: input.name.clone(),
+                });
+            } else {
+                let word = value_of("indexed", index, input)?.as_word();
+                topics.push(word.expect("unhashed indexed values fit in a word"));
+            }
+        }
+        Ok((topics, DynSolValue::Tuple(values).abi_encode_params()))
+    }
+
+    /// Like [`Parser::parse`], but also records the block number, transaction
+    /// hash, log index and address of the log in the event's `meta` object.
+    pub fn parse_with_meta(&self, log: &impl EventLog) -> Result<KeyedEvent, ParsingError> {
+        let mut event = self.parse(log)?;
+        event.meta = Some(log.meta());
+        Ok(event)
+    }
+
+    /// Decodes the revert data returned by a failed call. Errors defined in
+    /// the ABI take precedence over the builtin `Error(string)` and
+    /// `Panic(uint256)`. Arguments are keyed by the names of the error's
+    /// inputs, like the fields of events.
+    pub fn parse_error(&self, data: &[u8]) -> Result<KeyedEvent, ParsingError> {
+        let (selector, args) = split_selector(data)?;
+        let builtin;
+        let definition = match self.errors.get(&selector) {
+            Some(definition) => *definition,
+            None => {
+                builtin = builtin_error(selector).ok_or(ParsingError::UnknownError { selector })?;
+                &builtin
+            }
+        };
+
+        self.check_names(definition.inputs.iter().map(|p| &p.name))?;
+        let decoded = definition
+            .abi_decode_input(args, true)
+            .map_err(|error| decoding_failed(definition.signature(), error, None, data))?;
+        self.check_depth(&decoded)?;
+
+        Ok(self.keyed_args(
+            &definition.name,
+            selector,
+            || definition.signature(),
+            &definition.inputs,
+            decoded,
+        ))
+    }
+
+    /// Decodes transaction calldata into the called function's name and
+    /// arguments.
+    pub fn parse_call(&self, data: &[u8]) -> Result<KeyedEvent, ParsingError> {
+        let (selector, args) = split_selector(data)?;
+        let definition = self
+            .functions
+            .get(&selector)
+            .ok_or(ParsingError::UnknownFunction { selector })?;
+
+        self.check_names(definition.inputs.iter().map(|p| &p.name))?;
+        let decoded = definition
+            .abi_decode_input(args, true)
+            .map_err(|error| decoding_failed(definition.signature(), error, None, data))?;
+        self.check_depth(&decoded)?;
+
+        Ok(self.keyed_args(
+            &definition.name,
+            selector,
+            || definition.signature(),
+            &definition.inputs,
+            decoded,
+        ))
+    }
+
+    /// The [`KeyedEvent`] of decoded error or call arguments, keyed and
+    /// typed like the data of events.
+    fn keyed_args(
+        &self,
+        name: &str,
+        selector: Selector,
+        signature: impl FnOnce() -> String,
+        inputs: &[Param],
+        decoded: Vec<DynSolValue>,
+    ) -> KeyedEvent {
+        KeyedEvent {
+            name: name.to_string(),
+            selector: B256::right_padding_from(selector.as_slice()),
+            signature: self.options.include_signature.then(signature),
+            data: Value::Object(keyed_params(inputs, decoded, &self.options)),
+            types: self
+                .options
+                .include_types
+                .then(|| param_types(inputs, self.options.key_case)),
+            meta: None,
+            padded: false,
+            non_strict: false,
+            extra_topics: Vec::new(),
+            raw_topics: None,
+            raw_data: None,
+        }
+    }
+}
+
+/// A [`ParsingError::DecodingFailed`], capturing the input with the
+/// `capture-input-on-error` feature.
+fn decoding_failed(
+    signature: String,
+    error: alloy_dyn_abi::Error,
+    topics: Option<&[B256]>,
+    data: &[u8],
+) -> ParsingError {
+    let input = cfg!(feature = "capture-input-on-error").then(|| {
+        let mut input = Map::new();
+        if let Some(topics) = topics {
+            input.insert("topics".to_string(), serde_json::json!(topics));
+        }
+        let data = alloy_primitives::hex::encode_prefixed(data);
+        input.insert("data".to_string(), Value::String(data));
+        Box::new(Value::Object(input))
+    });
+    ParsingError::DecodingFailed {
+        signature,
+        error,
+        input,
+    }
+}
+
+/// The `input` of [`ParsingError::DecodingFailed`] in its message, if any.
+fn captured_input(input: &Option<Box<Value>>) -> String {
+    input
+        .as_ref()
+        .map_or_else(String::new, |input| format!(", input: {input}"))
+}
+
+/// The raw log, for [`OutputOptions::passthrough_unknown`].
+fn unknown_event(topics: &[B256], data: &[u8]) -> KeyedEvent {
+    let topic0 = topics.first().copied();
+    KeyedEvent {
+        name: "UnknownEvent".to_string(),
+        selector: topic0.unwrap_or_default(),
+        data: serde_json::json!({
+            "topic0": topic0,
+            "topics": topics,
+            "data": alloy_primitives::hex::encode_prefixed(data),
+        }),
+        ..Default::default()
+    }
+}
+
+/// The canonical Solidity type of every parameter, keyed like
+/// [`keyed_params`].
+fn param_types(params: &[Param], case: KeyCase) -> Map<String, Value> {
+    let params = params.iter().enumerate();
+    params
+        .map(|(i, param)| {
+            (
+                param_key(&param.name, i, case),
+                param.selector_type().into(),
+            )
+        })
+        .collect()
+}
+
+/// Splits calldata or revert data into its 4-byte selector and the encoded
+/// arguments.
+fn split_selector(data: &[u8]) -> Result<(Selector, &[u8]), ParsingError> {
+    if data.len() < 4 {
+        return Err(ParsingError::ShortCalldata { len: data.len() });
+    }
+    let (selector, args) = data.split_at(4);
+    Ok((Selector::from_slice(selector), args))
+}
+
+/// Pairs decoded values with the names of the parameters they were decoded
+/// from.
+fn keyed_params(
+    params: &[Param],
+    values: Vec<DynSolValue>,
+    options: &OutputOptions,
+) -> Map<String, Value> {
+    let map = params
+        .iter()
+        .enumerate()
+        .zip(values)
+        .map(|((i, k), v)| {
+            (
+                param_key(&k.name, i, options.key_case),
+                field_to_json(&k.name, v, &k.components, options),
+            )
+        })
+        .collect();
+    finish_map(map, options)
+}
+
+/// Applies the [`OutputOptions`] which restructure a whole object of fields.
+fn finish_map(mut map: Map<String, Value>, options: &OutputOptions) -> Map<String, Value> {
+    if options.flatten {
+        map = flatten(map, options.separator);
+    }
+    if options.sorted_keys {
+        map = sort_map(map);
+    }
+    map
+}
+
+/// Decodes arbitrary bytes as a log of an event in `abi` with the default
+/// options, as the body of a fuzz target, e.g. with `cargo fuzz`. Never
+/// panics: malformed topics or data are reported as errors.
+pub fn decode_fuzz(
+    abi: &JsonAbi,
+    topics: &[[u8; 32]],
+    data: &[u8],
+) -> Result<KeyedEvent, ParsingError> {
+    let topics = topics.iter().copied().map(B256::from).collect();
+    let log = alloy_primitives::LogData::new_unchecked(topics, data.to_vec().into());
+    Parser::new(abi).parse(&log)
+}
+
+/// Checks that the log has a topic for every indexed parameter, besides
+/// topic0 for non-anonymous events.
+fn check_topic_count(definition: &Event, topics: &[B256]) -> Result<(), ParsingError> {
+    let expected = definition.inputs.iter().filter(|i| i.indexed).count();
+    let found = topics.len().saturating_sub(!definition.anonymous as usize);
+    if expected != found {
+        return Err(ParsingError::TopicCountMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// The JSON of an indexed parameter stored as a hash: `{"hashed": ...}`, or
+/// for a struct `{"structHash": ..., "struct": "<StructName>"}`, the name
+/// only being known from the ABI's `internalType`.
+fn hashed_json(param: &EventParam, hash: B256) -> Value {
+    let (key, name) = hashed_shape(param);
+    let mut object = Map::new();
+    object.insert(key.to_string(), serde_json::json!(hash));
+    if let Some(name) = name {
+        object.insert("struct".to_string(), Value::String(name.to_string()));
+    }
+    Value::Object(object)
+}
+
+/// The key holding the hash of an indexed parameter for which [`is_hashed`]
+/// holds, and the struct name stored alongside it, as produced by
+/// [`hashed_json`] and described by [`Parser::event_schema`].
+pub(crate) fn hashed_shape(param: &EventParam) -> (&'static str, Option<&str>) {
+    if param.ty != "tuple" {
+        return ("hashed", None);
+    }
+    let name = param.internal_type.as_ref().and_then(|t| t.as_struct());
+    ("structHash", name.map(|(_, name)| name))
+}
+
+/// Whether an indexed parameter is stored in its topic as the keccak256 hash
+/// of its value rather than the value itself. This is the case for every type
+/// which doesn't fit in a single word: `string`, `bytes`, arrays and structs.
+pub(crate) fn is_hashed(param: &EventParam) -> bool {
+    !matches!(
+        param.resolve(),
+        Ok(DynSolType::Address
+            | DynSolType::Function
+            | DynSolType::Bool
+            | DynSolType::FixedBytes(_)
+            | DynSolType::Int(_)
+            | DynSolType::Uint(_))
+    )
+}
+
+/// Returns the definition of the errors the Solidity compiler emits on its
+/// own: `Error(string)` for `require`/`revert` and `Panic(uint256)` for
+/// assertion failures and arithmetic faults.
+fn builtin_error(selector: Selector) -> Option<AbiError> {
+    let (name, ty, input) = match selector.0 {
+        [0x08, 0xc3, 0x79, 0xa0] => ("Error", "string", "reason"),
+        [0x4e, 0x48, 0x7b, 0x71] => ("Panic", "uint256", "code"),
+        _ => return None,
+    };
+    Some(AbiError {
+        name: name.to_string(),
+        inputs: vec![Param {
+            ty: ty.to_string(),
+            name: input.to_string(),
+            components: vec![],
+            internal_type: None,
+        }],
+    })
+}
+
+#[cfg(all(test, feature = "ethers"))]
+mod tests {
+    use super::*;
+    use ethers::core::abi::ethabi::ethereum_types::H256;
+    use ethers::core::types::Log;
+    use serde::Deserialize;
+
+    fn logs() -> Vec<Log> {
+        #[derive(Deserialize)]
+        struct ApiResponse {
+            pub result: Vec<Log>,
+        }
+        let file = include_str!("../testdata/logs.json");
+        let response: ApiResponse = serde_json::from_str(file).unwrap();
+        response.result
+    }
+
+    fn erc20_abi() -> JsonAbi {
+        let json = include_str!("../testdata/erc20.json");
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn erc20_parsing_works() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        for log in logs() {
+            parser.parse(&log).unwrap();
+        }
+    }
+
+    #[test]
+    fn log_without_topics_is_an_error() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        log.topics.clear();
+        match parser.parse(&log) {
+            Err(ParsingError::MissingTopic0 { address }) => {
+                assert_eq!(address, Some(Address::from(log.address.0)))
+            }
+            other => panic!("expected MissingTopic0, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn selector_map_matches_linear_lookup() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        for log in logs() {
+            let linear = abi
+                .events()
+                .find(|e| e.selector().0 == log.topics[0].0)
+                .unwrap();
+            let mapped = &parser.events[&B256::from(log.topics[0].0)];
+            assert_eq!(mapped, &[linear]);
+
+            let decoded = parser.parse(&log).unwrap();
+            assert_eq!(decoded.name, linear.name);
+        }
+    }
+
+    #[test]
+    fn selector_is_serialized_as_hex() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let decoded = parser.parse(&logs()[0]).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+        assert_eq!(
+            json["selector"],
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        let roundtrip: KeyedEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(roundtrip.selector, decoded.selector);
+    }
+
+    #[test]
+    fn meta_is_attached() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let log = &logs()[0];
+
+        let plain = serde_json::to_value(parser.parse(log).unwrap()).unwrap();
+        assert!(plain.get("meta").is_none());
+
+        let decoded = parser.parse_with_meta(log).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+        assert_eq!(
+            json["meta"],
+            serde_json::json!({
+                "address": "0xea610b1153477720748dc13ed378003941d84fab",
+                "block_number": 0x5610fe,
+                "transaction_hash": format!("{:?}", log.transaction_hash.unwrap()),
+                "log_index": 10,
+            })
+        );
+    }
+
+    #[test]
+    fn indexed_fields_can_be_split() {
+        let abi = erc20_abi();
+        let log = &logs()[0];
+
+        let flat = Parser::new(&abi).parse(log).unwrap();
+        let flat = flat.data.as_object().unwrap();
+        assert_eq!(flat.keys().collect::<Vec<_>>(), vec!["from", "to", "value"]);
+
+        let options = OutputOptions {
+            split_indexed: true,
+            ..Default::default()
+        };
+        let split = Parser::with_options(&abi, options).parse(log).unwrap();
+        assert_eq!(split.data["indexed"]["from"], flat["from"]);
+        assert_eq!(split.data["indexed"]["to"], flat["to"]);
+        assert_eq!(split.data["body"]["value"], flat["value"]);
+        assert_eq!(split.data["indexed"].as_object().unwrap().len(), 2);
+        assert_eq!(split.data["body"].as_object().unwrap().len(), 1);
+    }
+
+    fn log(topics: Vec<B256>, data: Vec<u8>) -> Log {
+        Log {
+            topics: topics.into_iter().map(|t| H256(t.0)).collect(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn indexed_address_arrays_are_hashed() {
+        let mut abi = JsonAbi::new();
+        let event = Event::parse("Voted(address[] indexed voters, uint256 proposal)").unwrap();
+        abi.events.insert(event.name.clone(), vec![event.clone()]);
+
+        // Topics hold the hash of the padded elements, not an address.
+        let voters = [Address::repeat_byte(0xaa), Address::repeat_byte(0xbb)];
+        let encoded: Vec<u8> = voters
+            .iter()
+            .flat_map(|voter| voter.into_word().0)
+            .collect();
+        let hash = alloy_primitives::keccak256(encoded);
+        let proposal = DynSolValue::Uint(alloy_primitives::U256::from(3), 256);
+        let log = log(vec![event.selector(), hash], proposal.abi_encode());
+
+        let parser = Parser::new(&abi);
+        let decoded = parser.parse(&log).unwrap();
+        assert_eq!(
+            decoded.data["voters"],
+            serde_json::json!({ "hashed": hash.to_string() })
+        );
+        assert_eq!(decoded.data["proposal"], dyn_sol_to_json(proposal));
+
+        let schema = parser.event_schema("Voted").unwrap();
+        let voters = &schema["properties"]["voters"];
+        assert!(voters["properties"].get("hashed").is_some());
+    }
+
+    #[test]
+    fn indexed_structs_are_marked_as_struct_hashes() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "OrderPlaced",
+                "anonymous": false,
+                "inputs": [
+                    {
+                        "name": "order",
+                        "type": "tuple",
+                        "internalType": "struct Exchange.Order",
+                        "indexed": true,
+                        "components": [
+                            { "name": "maker", "type": "address" },
+                            { "name": "amount", "type": "uint256" }
+                        ]
+                    },
+                    {
+                        "name": "pair",
+                        "type": "tuple",
+                        "indexed": true,
+                        "components": [{ "name": "id", "type": "uint256" }]
+                    },
+                    {
+                        "name": "orders",
+                        "type": "tuple[]",
+                        "internalType": "struct Exchange.Order[]",
+                        "indexed": true,
+                        "components": [
+                            { "name": "maker", "type": "address" },
+                            { "name": "amount", "type": "uint256" }
+                        ]
+                    }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let event = abi.events().next().unwrap();
+        let hashes = [
+            B256::repeat_byte(1),
+            B256::repeat_byte(2),
+            B256::repeat_byte(3),
+        ];
+        let log = log([vec![event.selector()], hashes.to_vec()].concat(), vec![]);
+
+        let parser = Parser::new(&abi);
+        let data = parser.parse(&log).unwrap().data;
+        assert_eq!(
+            data["order"],
+            serde_json::json!({ "structHash": hashes[0].to_string(), "struct": "Order" })
+        );
+        assert_eq!(
+            data["pair"],
+            serde_json::json!({ "structHash": hashes[1].to_string() })
+        );
+        assert_eq!(
+            data["orders"],
+            serde_json::json!({ "hashed": hashes[2].to_string() })
+        );
+        assert_eq!(parser.decode_indexed(&log).unwrap()["order"], data["order"]);
+    }
+
+    #[test]
+    fn indexed_strings_are_marked_hashed() {
+        let abi = JsonAbi::parse(["event Named(string indexed name, uint256 value)"]).unwrap();
+        let parser = Parser::new(&abi);
+        let event = abi.events().next().unwrap();
+        let hash = alloy_primitives::keccak256("alice");
+        let data = DynSolValue::Uint(alloy_primitives::U256::from(7), 256).abi_encode();
+
+        let decoded = parser
+            .parse(&log(vec![event.selector(), hash], data))
+            .unwrap();
+        assert_eq!(
+            decoded.data["name"],
+            serde_json::json!({ "hashed": hash.to_string() })
+        );
+    }
+
+    #[test]
+    fn anonymous_events_are_matched_by_shape() {
+        let abi = JsonAbi::parse([
+            "event Pair(address indexed a, address indexed b) anonymous",
+            "event Single(address indexed who, bool flag) anonymous",
+        ])
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let who = B256::left_padding_from(&[0x42; 20]);
+        let data = DynSolValue::Bool(true).abi_encode();
+
+        let decoded = parser.parse_anonymous(&log(vec![who], data)).unwrap();
+        assert_eq!(decoded.name, "Single");
+        assert_eq!(decoded.data["flag"], true);
+
+        // Not found through the selector based lookup.
+        assert!(matches!(
+            parser.parse(&log(vec![who], vec![])),
+            Err(ParsingError::UnknownEvent { .. })
+        ));
+        assert!(matches!(
+            parser.parse_anonymous(&log(vec![who, who, who], vec![])),
+            Err(ParsingError::UnknownAnonymousEvent)
+        ));
+    }
+
+    #[test]
+    fn parse_by_event_name() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let log = &logs()[0];
+
+        let decoded = parser.parse_as("Transfer", log).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert!(matches!(
+            parser.parse_as("Approval", log),
+            Err(ParsingError::DecodingFailed { .. })
+        ));
+        assert!(matches!(
+            parser.parse_as("Mint", log),
+            Err(ParsingError::UnknownEventName { name }) if name == "Mint"
+        ));
+    }
+
+    #[test]
+    fn batch_parsing_keeps_going() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        let total = batch.len();
+        batch[1].topics[0] = H256::zero();
+        batch[2].data = vec![0xff].into();
+
+        let results = parser.parse_all(&batch);
+        assert_eq!(results.len(), total);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(ParsingError::UnknownEvent { .. })));
+        let err = results[2].as_ref().unwrap_err();
+        assert!(matches!(err, ParsingError::DecodingFailed { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("failed decoding Transfer(address,address,uint256): "));
+
+        let (events, errors) = parser.parse_known(&batch);
+        assert_eq!(events.len(), total - 2);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParsingError::DecodingFailed { .. }));
+    }
+
+    #[test]
+    fn parser_is_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<Parser<'_>>();
+    }
+
+    #[test]
+    #[cfg(feature = "stream")]
+    fn streamed_logs_are_decoded() {
+        use futures::StreamExt;
+
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        batch[1].topics[0] = H256::zero();
+        let stream = futures::stream::iter(batch.clone());
+        let streamed: Vec<_> = futures::executor::block_on(parser.parse_stream(stream).collect());
+
+        assert_eq!(streamed.len(), batch.len());
+        assert!(matches!(
+            streamed[1],
+            Err(ParsingError::UnknownEvent { .. })
+        ));
+        for (streamed, log) in streamed.iter().zip(&batch).filter(|(r, _)| r.is_ok()) {
+            assert_eq!(
+                streamed.as_ref().unwrap().data,
+                parser.parse(log).unwrap().data
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_parsing_matches_sequential() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        batch[1].topics[0] = H256::zero();
+
+        let render = |results: Vec<Result<KeyedEvent, ParsingError>>| -> Vec<String> {
+            results.into_iter().map(|r| format!("{r:?}")).collect()
+        };
+        assert_eq!(
+            render(parser.par_parse_all(&batch)),
+            render(parser.parse_all(&batch))
+        );
+    }
+
+    #[test]
+    fn nested_tuples_are_keyed_by_component_names() {
+        // Without `internalType`, alloy resolves these to plain tuples.
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "Sent",
+                "anonymous": false,
+                "inputs": [{
+                    "name": "packet",
+                    "type": "tuple",
+                    "indexed": false,
+                    "components": [
+                        { "name": "sequence", "type": "uint64" },
+                        {
+                            "name": "timeout",
+                            "type": "tuple",
+                            "components": [
+                                { "name": "revision", "type": "uint64" },
+                                { "name": "height", "type": "uint64" }
+                            ]
+                        }
+                    ]
+                }]
+            }]"#,
+        )
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let event = abi.events().next().unwrap();
+        let uint = |n: u64| DynSolValue::Uint(alloy_primitives::U256::from(n), 64);
+        let packet = DynSolValue::Tuple(vec![uint(1), DynSolValue::Tuple(vec![uint(2), uint(3)])]);
+        let data = DynSolValue::Tuple(vec![packet]).abi_encode_params();
+
+        let decoded = parser.parse(&log(vec![event.selector()], data)).unwrap();
+        let packet = &decoded.data["packet"];
+        assert_eq!(packet["sequence"], dyn_sol_to_json(uint(1)));
+        assert_eq!(packet["timeout"]["revision"], dyn_sol_to_json(uint(2)));
+        assert_eq!(packet["timeout"]["height"], dyn_sol_to_json(uint(3)));
+    }
+
+    #[test]
+    fn struct_arrays_are_keyed_by_component_names() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "SentBatch",
+                "anonymous": false,
+                "inputs": [{
+                    "name": "packets",
+                    "type": "tuple[]",
+                    "internalType": "struct Packet[]",
+                    "indexed": false,
+                    "components": [
+                        { "name": "sequence", "type": "uint64" },
+                        {
+                            "name": "hops",
+                            "type": "tuple[2]",
+                            "components": [
+                                { "name": "port", "type": "string" },
+                                { "name": "channel", "type": "string" }
+                            ]
+                        }
+                    ]
+                }]
+            }]"#,
+        )
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let event = abi.events().next().unwrap();
+        let uint = |n: u64| DynSolValue::Uint(alloy_primitives::U256::from(n), 64);
+        let string = |s: &str| DynSolValue::String(s.to_string());
+        let hop = |port: &str| DynSolValue::Tuple(vec![string(port), string("channel-0")]);
+        let packet = |n: u64| {
+            DynSolValue::Tuple(vec![
+                uint(n),
+                DynSolValue::FixedArray(vec![hop("transfer"), hop("icahost")]),
+            ])
+        };
+        let packets = DynSolValue::Array(vec![packet(1), packet(2)]);
+        let data = DynSolValue::Tuple(vec![packets]).abi_encode_params();
+
+        let decoded = parser
+            .parse(&log(vec![event.selector()], data.clone()))
+            .unwrap();
+        let packets = decoded.data["packets"].as_array().unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[1]["sequence"], dyn_sol_to_json(uint(2)));
+        assert_eq!(packets[0]["hops"][1]["port"], "icahost");
+        assert_eq!(packets[0]["hops"][0]["channel"], "channel-0");
+
+        let (_, encoded) = parser.encode(&decoded).unwrap();
+        assert_eq!(encoded, data);
+    }
+
+    #[test]
+    #[cfg(not(feature = "arbitrary-precision"))]
+    fn typed_decode() {
+        #[derive(Deserialize)]
+        struct Transfer {
+            from: String,
+            to: String,
+            value: String,
+        }
+
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let transfer: Transfer = parser.parse_typed(&logs()[0]).unwrap();
+        assert_eq!(transfer.from, "0x8a02604a33da84F492d161c8C9fc5068f368e352");
+        assert_eq!(transfer.to.len(), 42);
+        assert_eq!(transfer.value, "100000000000000000000");
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Mismatch {
+            owner: String,
+        }
+        assert!(matches!(
+            parser.parse_typed::<Mismatch>(&logs()[0]),
+            Err(ParsingError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn selector_collisions_try_every_candidate() {
+        // ERC721 and ERC20 share the Transfer selector, but differ in which
+        // parameters are indexed.
+        let abi = JsonAbi::parse([
+            "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ])
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let log = &logs()[0];
+
+        let decoded = parser.parse(log).unwrap();
+        assert!(decoded.data.get("value").is_some());
+
+        let mut broken = log.clone();
+        broken.data = vec![0xff].into();
+        assert!(matches!(
+            parser.parse(&broken),
+            Err(ParsingError::AmbiguousOrFailed { tried: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn builder_options_take_effect() {
+        let abi = erc20_abi();
+        let parser = Parser::builder()
+            .address_case(AddressCase::Lowercase)
+            .split_indexed(true)
+            .include_signature(true)
+            .build(&abi);
+        let event = parser.parse(&logs()[0]).unwrap();
+        assert_eq!(
+            event.signature.as_deref(),
+            Some("Transfer(address,address,uint256)")
+        );
+        let from = event.data["indexed"]["from"].as_str().unwrap();
+        assert_eq!(from, from.to_lowercase());
+        assert!(event.data["body"].get("value").is_some());
+    }
+
+    #[test]
+    fn events_are_looked_up_by_name() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        assert_eq!(parser.names["Transfer"].len(), 1);
+        let log = &logs()[0];
+        let event = parser.parse_as("Transfer", log).unwrap();
+        assert_eq!(event.selector, parser.names["Transfer"][0].selector());
+        assert_eq!(event.data, parser.parse(log).unwrap().data);
+    }
+
+    #[test]
+    fn raw_rpc_logs_are_decoded() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let rpc = serde_json::json!({
+            "address": "0x5fbdb2315678afecb367f032d93f642f64180aa3",
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                "0x0000000000000000000000001111111111111111111111111111111111111111",
+                "0x0000000000000000000000002222222222222222222222222222222222222222"
+            ],
+            "data": "0x00000000000000000000000000000000000000000000000000000000000001f4",
+            "blockNumber": "0x10",
+            "logIndex": "0x0",
+            "removed": false
+        });
+        let event = parser.parse_rpc(&rpc).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(
+            event.data["from"],
+            Address::repeat_byte(0x11).to_checksum(None)
+        );
+        assert_eq!(
+            event.data["value"],
+            dyn_sol_to_json(DynSolValue::Uint(alloy_primitives::U256::from(500), 256))
+        );
+
+        assert!(matches!(
+            parser.parse_rpc(&serde_json::json!({ "topics": "0x" })),
+            Err(ParsingError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn function_values_are_hex() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "Callback",
+                "anonymous": false,
+                "inputs": [{ "name": "callback", "type": "function", "indexed": false }]
+            }]"#,
+        )
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let event = abi.events().next().unwrap();
+        let mut function = [0u8; 24];
+        function[..20].copy_from_slice(&[0x11; 20]);
+        function[20..].copy_from_slice(&[0xa9, 0x05, 0x9c, 0xbb]);
+
+        let decoded = parser
+            .parse(&log(
+                vec![event.selector()],
+                B256::right_padding_from(&function).to_vec(),
+            ))
+            .unwrap();
+        assert_eq!(
+            decoded.data["callback"],
+            "0x1111111111111111111111111111111111111111a9059cbb"
+        );
+    }
+
+    #[test]
+    fn unnamed_parameters_are_positional() {
+        let abi = JsonAbi::parse(["event Pair(address indexed, uint256, uint256 named)"]).unwrap();
+        let event = abi.events().next().unwrap();
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(alloy_primitives::U256::from(1), 256),
+            DynSolValue::Uint(alloy_primitives::U256::from(2), 256),
+        ])
+        .abi_encode_params();
+        let log = log(vec![event.selector(), Address::ZERO.into_word()], data);
+
+        let parser = Parser::new(&abi);
+        let decoded = parser.parse(&log).unwrap();
+        let keys: Vec<_> = decoded.data.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["arg0", "arg1", "named"]);
+        let (topics, _) = parser.encode(&decoded).unwrap();
+        assert_eq!(topics, log.topics().into_owned());
+
+        let options = OutputOptions {
+            unnamed_parameters: UnnamedParameters::Reject,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Parser::with_options(&abi, options).parse(&log),
+            Err(ParsingError::UnnamedParameter { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn nesting_beyond_the_depth_limit_is_rejected() {
+        let mut abi = JsonAbi::new();
+        let event = Event::parse("Grid(uint256[][][] cells)").unwrap();
+        abi.events.insert(event.name.clone(), vec![event.clone()]);
+        let cell = DynSolValue::Uint(alloy_primitives::U256::from(1), 256);
+        let cells = DynSolValue::Array(vec![DynSolValue::Array(vec![DynSolValue::Array(vec![
+            cell,
+        ])])]);
+        let log = log(vec![event.selector()], cells.abi_encode_params());
+
+        assert!(Parser::new(&abi).parse(&log).is_ok());
+        let shallow = ParserBuilder::new().max_depth(2).build(&abi);
+        assert!(matches!(
+            shallow.parse(&log),
+            Err(ParsingError::DepthExceeded {
+                depth: 3,
+                max_depth: 2
+            })
+        ));
+        assert!(shallow.parse_ref(&log).is_err());
+        assert!(shallow.parse_columns(&log).is_err());
+        assert!(ParserBuilder::new()
+            .max_depth(3)
+            .build(&abi)
+            .parse(&log)
+            .is_ok());
+    }
+
+    #[test]
+    fn events_without_indexed_parameters() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "Deposit",
+                "anonymous": false,
+                "inputs": [
+                    { "name": "account", "type": "address", "indexed": false },
+                    { "name": "amount", "type": "uint256", "indexed": false }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let event = abi.events().next().unwrap();
+        let account = Address::repeat_byte(0x11);
+        let amount = alloy_primitives::U256::from(5);
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Address(account),
+            DynSolValue::Uint(amount, 256),
+        ])
+        .abi_encode_params();
+        let deposit = log(vec![event.selector()], data);
+
+        let options = OutputOptions {
+            split_indexed: true,
+            ..Default::default()
+        };
+        let decoded = Parser::with_options(&abi, options).parse(&deposit).unwrap();
+        assert_eq!(decoded.name, "Deposit");
+        assert_eq!(decoded.data["indexed"], serde_json::json!({}));
+        assert_eq!(
+            decoded.data["body"],
+            serde_json::json!({
+                "account": dyn_sol_to_json(DynSolValue::Address(account)),
+                "amount": dyn_sol_to_json(DynSolValue::Uint(amount, 256)),
+            })
+        );
+
+        // topic0 is the selector, not a value for the first parameter.
+        let stray = log(vec![event.selector(), B256::ZERO], deposit.data.to_vec());
+        assert!(Parser::new(&abi).parse(&stray).is_err());
+    }
+
+    #[test]
+    fn only_indexed_fields_are_decoded() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        let log = logs()
+            .into_iter()
+            .find(|log| log.topics[0].0 == transfer.0)
+            .unwrap();
+
+        let indexed = parser.decode_indexed(&log).unwrap();
+        let keys: Vec<_> = indexed.keys().collect();
+        assert_eq!(keys, ["from", "to"]);
+        let full = parser.parse(&log).unwrap();
+        assert_eq!(indexed["from"], full.data["from"]);
+        assert_eq!(indexed["to"], full.data["to"]);
+    }
+
+    #[test]
+    fn overloaded_events_decode_by_selector() {
+        let abi = JsonAbi::parse([
+            "event Sync(uint112 reserve0, uint112 reserve1)",
+            "event Sync(uint256 reserve)",
+        ])
+        .unwrap();
+        let options = OutputOptions {
+            include_signature: true,
+            ..Default::default()
+        };
+        let parser = Parser::with_options(&abi, options);
+        assert_eq!(parser.names["Sync"].len(), 2);
+
+        let uint = |n: u64, bits| DynSolValue::Uint(alloy_primitives::U256::from(n), bits);
+        let pair = &abi.event("Sync").unwrap()[0];
+        let single = &abi.event("Sync").unwrap()[1];
+        assert_ne!(pair.selector(), single.selector());
+
+        let data = DynSolValue::Tuple(vec![uint(1, 112), uint(2, 112)]).abi_encode_params();
+        let decoded = parser.parse(&log(vec![pair.selector()], data)).unwrap();
+        assert_eq!(decoded.signature.as_deref(), Some("Sync(uint112,uint112)"));
+        assert!(decoded.data.get("reserve1").is_some());
+
+        let data = DynSolValue::Tuple(vec![uint(3, 256)]).abi_encode_params();
+        let log = log(vec![single.selector()], data);
+        let decoded = parser.parse(&log).unwrap();
+        assert_eq!(decoded.signature.as_deref(), Some("Sync(uint256)"));
+        assert_eq!(decoded.data["reserve"], dyn_sol_to_json(uint(3, 256)));
+
+        // By name, the overload matching the selector is found.
+        let by_name = parser.parse_as("Sync", &log).unwrap();
+        assert_eq!(by_name.selector, single.selector());
+    }
+
+    #[test]
+    fn transfer_schema() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let schema = parser.event_schema("Transfer").unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["from", "to", "value"])
+        );
+        assert_eq!(schema["properties"]["from"]["type"], "string");
+        assert!(matches!(
+            parser.event_schema("Mint"),
+            Err(ParsingError::UnknownEventName { .. })
+        ));
+
+        let options = OutputOptions {
+            split_indexed: true,
+            numbers_as_json_when_small: true,
+            ..Default::default()
+        };
+        let schema = Parser::with_options(&abi, options)
+            .event_schema("Transfer")
+            .unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["body", "indexed"]));
+        let value = &schema["properties"]["body"]["properties"]["value"];
+        let integer = serde_json::json!({ "type": "integer" });
+        if cfg!(feature = "arbitrary-precision") {
+            assert_eq!(value, &integer);
+        } else {
+            assert_eq!(value["anyOf"][0], integer);
+        }
+    }
+
+    #[test]
+    fn output_buffer_is_reused() {
+        let abi = erc20_abi();
+        let options = OutputOptions {
+            include_signature: true,
+            ..Default::default()
+        };
+        let parser = Parser::with_options(&abi, options);
+        let mut out = KeyedEvent::default();
+        for log in logs() {
+            parser.parse_into(&log, &mut out).unwrap();
+            let expected = parser.parse(&log).unwrap();
+            assert_eq!(
+                serde_json::to_value(&out).unwrap(),
+                serde_json::to_value(&expected).unwrap()
+            );
+        }
+
+        let before = serde_json::to_value(&out).unwrap();
+        assert!(parser.parse_into(&Log::default(), &mut out).is_err());
+        assert_eq!(serde_json::to_value(&out).unwrap(), before);
+    }
+
+    #[test]
+    fn parameter_types_are_included() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new().include_types(true).build(&abi);
+        let transfer = parser
+            .parse_all(&logs())
+            .into_iter()
+            .flatten()
+            .find(|event| event.name == "Transfer")
+            .unwrap();
+        let types = transfer.types.unwrap();
+        assert_eq!(types["value"], "uint256");
+        assert_eq!(types["from"], "address");
+
+        let abi = JsonAbi::parse(["event Batch(uint256[] ids, (uint256,bytes) pair)"]).unwrap();
+        let parser = ParserBuilder::new().include_types(true).build(&abi);
+        let event = &abi.events().next().unwrap();
+        let (topics, data) = parser
+            .encode(&KeyedEvent {
+                name: "Batch".to_string(),
+                selector: event.selector(),
+                data: serde_json::json!({ "ids": [], "pair": ["1", ""] }),
+                ..Default::default()
+            })
+            .unwrap();
+        let log = alloy_primitives::LogData::new(topics, data.into()).unwrap();
+        let types = parser.parse(&log).unwrap().types.unwrap();
+        assert_eq!(types["ids"], "uint256[]");
+        assert_eq!(types["pair"], "(uint256,bytes)");
+        assert!(Parser::new(&abi).parse(&log).unwrap().types.is_none());
+    }
+
+    #[test]
+    fn names_are_borrowed_from_the_abi() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let transfer = &abi.event("Transfer").unwrap()[0];
+        for log in logs() {
+            let owned = parser.parse(&log).unwrap();
+            let borrowed = parser.parse_ref(&log).unwrap();
+            assert_eq!(borrowed.name, owned.name);
+            assert_eq!(borrowed.data, owned.data);
+            if borrowed.name == "Transfer" {
+                assert!(core::ptr::eq(borrowed.name, transfer.name.as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn stray_data_on_indexed_only_events() {
+        let abi = JsonAbi::parse([
+            "event Approval(address indexed owner, address indexed spender, uint256 indexed id)",
+        ])
+        .unwrap();
+        let event = abi.events().next().unwrap();
+        let topics = vec![
+            event.selector(),
+            B256::left_padding_from(&[1]),
+            B256::left_padding_from(&[2]),
+            B256::left_padding_from(&[3]),
+        ];
+        let parser = Parser::new(&abi);
+        assert!(parser.parse(&log(topics.clone(), vec![])).is_ok());
+
+        let stray = log(topics, vec![0; 32]);
+        let err = parser.parse(&stray).unwrap_err();
+        assert!(matches!(err, ParsingError::UnexpectedData { len: 32, .. }));
+        assert!(err
+            .to_string()
+            .starts_with("Approval(address,address,uint256)"));
+
+        let lax = ParserBuilder::new().strict(false).build(&abi);
+        let decoded = serde_json::to_value(lax.parse(&stray).unwrap()).unwrap();
+        assert_eq!(decoded["non_strict"], true);
+    }
+
+    #[test]
+    fn keyed_events_round_trip_through_json() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new()
+            .include_signature(true)
+            .include_types(true)
+            .build(&abi);
+        let log = &logs()[0];
+        for event in [
+            parser.parse(log).unwrap(),
+            parser.parse_with_meta(log).unwrap(),
+        ] {
+            let json = serde_json::to_string(&event).unwrap();
+            let back: KeyedEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.meta, event.meta);
+            assert_eq!(
+                serde_json::to_value(&back).unwrap(),
+                serde_json::to_value(&event).unwrap()
+            );
+        }
+
+        // Optional fields are left out rather than written as `null`.
+        let plain = serde_json::to_value(Parser::new(&abi).parse(log).unwrap()).unwrap();
+        let mut keys: Vec<_> = plain.as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["data", "name", "selector"]);
+        let back: KeyedEvent = serde_json::from_value(plain).unwrap();
+        assert!(back.meta.is_none() && back.signature.is_none() && back.types.is_none());
+    }
+
+    #[test]
+    fn decoding_twice_gives_equal_events() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let logs = logs();
+        assert_eq!(
+            parser.parse(&logs[0]).unwrap(),
+            parser.parse(&logs[0]).unwrap()
+        );
+        assert_ne!(
+            parser.parse(&logs[0]).unwrap(),
+            parser.parse(&logs[1]).unwrap()
+        );
+
+        let mut with_meta = parser.parse(&logs[0]).unwrap();
+        with_meta.meta = Some(LogMeta::default());
+        assert_ne!(with_meta, parser.parse(&logs[0]).unwrap());
+    }
+
+    #[test]
+    fn events_convert_to_values() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new()
+            .include_signature(true)
+            .include_types(true)
+            .build(&abi);
+        let log = &logs()[0];
+        for event in [
+            Parser::new(&abi).parse(log).unwrap(),
+            parser.parse_with_meta(log).unwrap(),
+        ] {
+            let expected = serde_json::to_value(&event).unwrap();
+            assert_eq!(event.as_value(), expected);
+            assert_eq!(event.into_value(), expected);
+        }
+
+        let value = parser.parse_with_meta(log).unwrap().into_value();
+        assert_eq!(value["name"], "Transfer");
+        assert_eq!(value["signature"], "Transfer(address,address,uint256)");
+        assert!(value["selector"]
+            .as_str()
+            .unwrap()
+            .starts_with("0xddf252ad"));
+        assert!(value["data"].get("value").is_some());
+        assert!(value["meta"].get("transaction_hash").is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "msgpack")]
+    fn events_round_trip_through_msgpack() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new()
+            .include_signature(true)
+            .include_types(true)
+            .build(&abi);
+        for log in logs() {
+            let event = parser.parse_with_meta(&log).unwrap();
+            let bytes = event.to_bytes().unwrap();
+            assert!(bytes.len() < serde_json::to_vec(&event).unwrap().len());
+            let back = KeyedEvent::from_bytes(&bytes).unwrap();
+            assert_eq!(back.as_value(), event.as_value());
+        }
+
+        // Integers beyond `u64` are kept exactly.
+        let large = DynSolValue::Uint(alloy_primitives::U256::MAX, 256);
+        let event = KeyedEvent {
+            name: "Large".to_string(),
+            data: serde_json::json!({ "value": dyn_sol_to_json(large) }),
+            ..Default::default()
+        };
+        let back = KeyedEvent::from_bytes(&event.to_bytes().unwrap()).unwrap();
+        assert_eq!(back.data, event.data);
+        assert!(KeyedEvent::from_bytes(&[0xc1]).is_err());
+    }
+
+    #[test]
+    fn transfer_amounts_are_scaled() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new().decimals("value", 18).build(&abi);
+        let mut log = logs().remove(0);
+        let amount = alloy_primitives::U256::from(1_500_000_000_000_000_000u64);
+        log.data = amount.to_be_bytes_vec().into();
+
+        let data = parser.parse(&log).unwrap().data;
+        assert_eq!(data["value"], "1.5");
+        let schema = parser.event_schema("Transfer").unwrap();
+        assert!(schema["properties"]["value"]["pattern"]
+            .as_str()
+            .unwrap()
+            .contains("\\."));
+        assert_eq!(
+            data["from"],
+            Parser::new(&abi).parse(&log).unwrap().data["from"]
+        );
+
+        // Whole-token amounts are emitted without a fraction.
+        log.data = alloy_primitives::U256::from(10u64.pow(18))
+            .to_be_bytes_vec()
+            .into();
+        let event = parser.parse(&log).unwrap();
+        assert_eq!(event.data["value"], "1");
+        let (_, data) = parser.encode(&event).unwrap();
+        assert_eq!(data, log.data.to_vec());
+    }
+
+    #[test]
+    fn proxy_abis_are_tried_in_order() {
+        let abis = [
+            JsonAbi::parse(["event Upgraded(address indexed implementation, bool paused)"])
+                .unwrap(),
+            JsonAbi::parse(["event Upgraded(address indexed implementation, bool indexed paused)"])
+                .unwrap(),
+        ];
+        let parser = Parser::from_abis(&abis);
+        let selector = abis[0].events().next().unwrap().selector();
+        let implementation = B256::left_padding_from(&[0xaa; 20]);
+        let paused = DynSolValue::Bool(true).abi_encode();
+
+        let old = log(vec![selector, implementation], paused.clone());
+        let (event, index) = parser.parse_with_abi_index(&old).unwrap();
+        assert_eq!(
+            (event.data["paused"].clone(), index),
+            (Value::Bool(true), 0)
+        );
+
+        // Only the newer layout decodes a log with `paused` as a topic.
+        let new = log(
+            vec![selector, implementation, B256::from_slice(&paused)],
+            vec![],
+        );
+        let (event, index) = parser.parse_with_abi_index(&new).unwrap();
+        assert_eq!(
+            (event.data["paused"].clone(), index),
+            (Value::Bool(true), 1)
+        );
+        assert!(Parser::new(&abis[0]).parse(&new).is_err());
+    }
+
+    #[test]
+    fn erc20_events_are_typed() {
+        let abi = erc20_abi();
+        let raw = Parser::new(&abi);
+        let parsers = [
+            Parser::new(&abi),
+            ParserBuilder::new()
+                .split_indexed(true)
+                .int_encoding(IntEncoding::Hex)
+                .address_case(AddressCase::Lowercase)
+                .build(&abi),
+        ];
+        let mut transfers = 0;
+        for log in logs() {
+            let values: Vec<_> = raw
+                .decode(&log)
+                .unwrap()
+                .values
+                .into_iter()
+                .map(|f| f.value)
+                .collect();
+            for parser in &parsers {
+                let event = parser.parse(&log).unwrap();
+                let typed = match (event.as_transfer(), event.as_approval()) {
+                    (Some(t), None) => (t.from, t.to, t.value),
+                    (None, Some(a)) => (a.owner, a.spender, a.value),
+                    other => panic!("{} decoded as {other:?}", event.name),
+                };
+                let expected = (
+                    values[0].as_address().unwrap(),
+                    values[1].as_address().unwrap(),
+                    values[2].as_uint().unwrap().0,
+                );
+                assert_eq!(typed, expected);
+                transfers += event.as_transfer().is_some() as usize;
+            }
+        }
+        assert!(transfers > 0);
+    }
+
+    #[test]
+    fn events_are_serialized_to_strings() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let log = &logs()[0];
+        let event = serde_json::to_value(parser.parse(log).unwrap()).unwrap();
+
+        let compact = parser.parse_to_string(log).unwrap();
+        assert!(!compact.contains('\n'));
+        assert_eq!(serde_json::from_str::<Value>(&compact).unwrap(), event);
+
+        let pretty = parser.parse_to_string_pretty(log).unwrap();
+        assert!(pretty.lines().count() > 1);
+        assert_eq!(serde_json::from_str::<Value>(&pretty).unwrap(), event);
+    }
+
+    #[test]
+    fn transfer_columns_are_in_abi_order() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let log = &logs()[0];
+        let event = parser.parse(log).unwrap();
+
+        let columns = parser.parse_columns(log).unwrap();
+        let names: Vec<_> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["from", "to", "value"]);
+        for (name, value) in &columns {
+            assert_eq!(value, &event.data[name]);
+        }
+    }
+
+    #[test]
+    fn raw_topics_are_included_on_request() {
+        let abi = erc20_abi();
+        let log = &logs()[0];
+        let topics: Vec<_> = log
+            .topics
+            .iter()
+            .map(|t| B256::from(t.0).to_string())
+            .collect();
+        let data = alloy_primitives::hex::encode_prefixed(&log.data);
+
+        let event = ParserBuilder::new()
+            .split_indexed(true)
+            .include_raw_topics(true)
+            .build(&abi)
+            .parse(log)
+            .unwrap();
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["raw_topics"], serde_json::json!(topics));
+        assert_eq!(json["raw_data"], data);
+        assert!(json["data"].get("raw_topics").is_none());
+        assert!(json["data"]["indexed"].get("from").is_some());
+        assert_eq!(event.as_value(), json);
+
+        let json = serde_json::to_value(Parser::new(&abi).parse(log).unwrap()).unwrap();
+        assert!(json.get("raw_topics").is_none());
+        assert!(json.get("raw_data").is_none());
+    }
+
+    #[test]
+    fn transactions_are_summarized_by_event() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        // Approvals have the same shape as transfers.
+        let mut approval = batch[0].clone();
+        let selector = abi
+            .events()
+            .find(|e| e.name == "Approval")
+            .unwrap()
+            .selector();
+        approval.topics[0] = H256(selector.0);
+        let mut unknown = batch[0].clone();
+        unknown.topics[0] = H256::repeat_byte(0xab);
+        batch.extend([approval, unknown]);
+
+        let summary = parser.parse_tx_summary(&batch);
+        let mut keys: Vec<_> = summary.as_object().unwrap().keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["Approval", "Transfer", "unknown"]);
+
+        let transfers: Vec<_> = batch
+            .iter()
+            .filter_map(|log| parser.parse(log).ok())
+            .filter(|event| event.name == "Transfer")
+            .map(|event| event.data)
+            .collect();
+        assert_eq!(transfers.len(), batch.len() - 2);
+        assert_eq!(summary["Transfer"], Value::Array(transfers));
+        assert_eq!(summary["Approval"].as_array().unwrap().len(), 1);
+        let unknown = summary["unknown"].as_array().unwrap();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0]["topic0"], format!("0x{}", "ab".repeat(32)));
+    }
+
+    #[test]
+    fn transfer_params_in_etherscan_style() {
+        let abi = erc20_abi();
+        let log = &logs()[0];
+        let keyed = Parser::new(&abi).parse(log).unwrap().data;
+
+        let parser = ParserBuilder::new()
+            .etherscan_style(true)
+            .split_indexed(true)
+            .build(&abi);
+        let event = parser.parse(log).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(
+            event.data,
+            serde_json::json!({
+                "params": [
+                    { "name": "from", "type": "address", "value": keyed["from"], "indexed": true },
+                    { "name": "to", "type": "address", "value": keyed["to"], "indexed": true },
+                    { "name": "value", "type": "uint256", "value": keyed["value"], "indexed": false },
+                ]
+            })
+        );
+
+        let schema = parser.event_schema("Transfer").unwrap();
+        let keyed_schema = Parser::new(&abi).event_schema("Transfer").unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["params"]));
+        let params = &schema["properties"]["params"];
+        assert_eq!(params["minItems"], 3);
+        for (param, emitted) in params["prefixItems"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(event.data["params"].as_array().unwrap())
+        {
+            let properties = &param["properties"];
+            assert_eq!(properties["name"]["const"], emitted["name"]);
+            assert_eq!(properties["type"]["const"], emitted["type"]);
+            assert_eq!(properties["indexed"]["const"], emitted["indexed"]);
+            let name = emitted["name"].as_str().unwrap();
+            assert_eq!(properties["value"], keyed_schema["properties"][name]);
+        }
+    }
+
+    #[test]
+    fn matched_definition_is_returned() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        for log in logs() {
+            let (event, definition) = parser.parse_with_def(&log).unwrap();
+            assert_eq!(definition.name, event.name);
+            assert_eq!(definition.selector(), event.selector);
+        }
+    }
+
+    #[test]
+    fn logs_are_paired_with_their_events() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let logs = logs();
+        for log in &logs {
+            let (event, paired) = parser.parse_paired(log).unwrap();
+            assert!(core::ptr::eq(paired, log));
+            assert_eq!(event.selector, B256::from(paired.topics[0].0));
+        }
+    }
+
+    #[test]
+    fn transfer_is_decoded_from_raw_types() {
+        let log = &logs()[0];
+        let topics: Vec<B256> = log.topics.iter().map(|t| B256::from(t.0)).collect();
+        let types = [
+            DynSolType::Address,
+            DynSolType::Address,
+            DynSolType::Uint(256),
+        ];
+
+        let values =
+            Parser::try_decode_raw(&topics[1..], &log.data, &types, &[true, true, false]).unwrap();
+        let abi = erc20_abi();
+        let decoded = Parser::new(&abi).decode(log).unwrap();
+        let expected: Vec<_> = decoded.values.into_iter().map(|f| f.value).collect();
+        assert_eq!(values, expected);
+
+        let err = Parser::try_decode_raw(&topics, &log.data, &types, &[true, true, false]);
+        assert!(matches!(
+            err,
+            Err(alloy_dyn_abi::Error::TopicLengthMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+
+        let err = Parser::try_decode_raw(&topics[1..], &log.data, &types, &[true, true]);
+        assert!(matches!(
+            err,
+            Err(alloy_dyn_abi::Error::EncodeLengthMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn raw_values_are_exposed() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let decoded = parser.decode(&logs()[0]).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+
+        let names: Vec<_> = decoded.values.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["from", "to", "value"]);
+        let indexed: Vec<_> = decoded.values.iter().map(|f| f.indexed).collect();
+        assert_eq!(indexed, [true, true, false]);
+        assert!(matches!(decoded.values[0].value, DynSolValue::Address(_)));
+        assert!(matches!(decoded.values[1].value, DynSolValue::Address(_)));
+        assert_eq!(
+            decoded.values[2].value,
+            DynSolValue::Uint(
+                alloy_primitives::U256::from(100_000_000_000_000_000_000u128),
+                256
+            )
+        );
+    }
+
+    #[test]
+    fn signature_is_included_on_request() {
+        let abi = erc20_abi();
+        let log = &logs()[0];
+        assert_eq!(Parser::new(&abi).parse(log).unwrap().signature, None);
+
+        let options = OutputOptions {
+            include_signature: true,
+            ..Default::default()
+        };
+        let decoded = Parser::with_options(&abi, options).parse(log).unwrap();
+        assert_eq!(
+            decoded.signature.as_deref(),
+            Some("Transfer(address,address,uint256)")
+        );
+        assert_eq!(
+            alloy_primitives::keccak256(decoded.signature.unwrap()),
+            decoded.selector
+        );
+    }
+
+    #[test]
+    fn failed_input_is_captured() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        log.data = vec![0xff].into();
+
+        let Err(ParsingError::DecodingFailed { input, .. }) = parser.parse(&log) else {
+            panic!("expected a decoding failure");
+        };
+        if !cfg!(feature = "capture-input-on-error") {
+            assert!(input.is_none());
+            return;
+        }
+        let input = input.unwrap();
+        let topic0 = serde_json::to_value(B256::from(log.topics[0].0)).unwrap();
+        assert_eq!(input["topics"][0], topic0);
+        assert_eq!(input["data"], "0xff");
+
+        let err = parser.parse(&log).unwrap_err().to_string();
+        assert!(err.contains(topic0.as_str().unwrap()));
+    }
+
+    #[test]
+    fn fuzz_entry_point_rejects_malformed_logs() {
+        let abi = erc20_abi();
+        let log = &logs()[0];
+        let topics: Vec<[u8; 32]> = log.topics.iter().map(|t| t.0).collect();
+
+        let event = decode_fuzz(&abi, &topics, &log.data).unwrap();
+        assert_eq!(event, Parser::new(&abi).parse(log).unwrap());
+
+        assert!(matches!(
+            decode_fuzz(&abi, &[], &log.data),
+            Err(ParsingError::MissingTopic0 { .. })
+        ));
+        assert!(decode_fuzz(&abi, &topics[..2], &log.data).is_err());
+        assert!(decode_fuzz(&abi, &[topics[0]; 8], &log.data).is_err());
+        assert!(decode_fuzz(&abi, &topics, &[]).is_err());
+        assert!(decode_fuzz(&abi, &topics, &log.data[..31]).is_err());
+        assert!(decode_fuzz(&abi, &[[0xff; 32]], &[0xff; 1024]).is_err());
+    }
+
+    #[test]
+    fn unknown_events_are_none() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        assert!(parser.parse_opt(&log).unwrap().is_some());
+
+        log.data = vec![0xff].into();
+        assert!(matches!(
+            parser.parse_opt(&log),
+            Err(ParsingError::DecodingFailed { .. })
+        ));
+
+        log.topics[0] = H256::zero();
+        assert!(parser.parse_opt(&log).unwrap().is_none());
+    }
+
+    #[test]
+    fn failures_are_explained() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        let explanation = parser.explain_failure(&log);
+        assert!(explanation.starts_with("Transfer(address,address,uint256):"));
+        assert!(explanation.contains("decodes with the configured options"));
+
+        log.topics.pop();
+        let explanation = parser.explain_failure(&log);
+        assert!(
+            explanation.contains(
+                "topics: expected 3 (topic0 and 2 indexed: address, address), found 2: mismatch"
+            ),
+            "{explanation}"
+        );
+        assert!(explanation.contains("fails to decode: expected 2 indexed topics, found 1"));
+
+        log.data = vec![0; 31].into();
+        let explanation = parser.explain_failure(&log);
+        assert!(
+            explanation.contains("data: expected at least 32 bytes (uint256), found 31: too short")
+        );
+
+        log.topics[0] = H256::repeat_byte(0xab);
+        assert!(parser
+            .explain_failure(&log)
+            .contains("matches no event in the abi"));
+    }
+
+    #[test]
+    fn topic_count_is_validated() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        log.topics.pop();
+        assert!(matches!(
+            parser.parse(&log),
+            Err(ParsingError::TopicCountMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn extra_topics_are_kept_when_not_strict() {
+        let abi = JsonAbi::parse(["event Ping(uint256 indexed id, bool ok)"]).unwrap();
+        let event = abi.events().next().unwrap();
+        let extra = B256::repeat_byte(7);
+        let topics = vec![event.selector(), B256::with_last_byte(1), extra];
+        let log = log(topics, DynSolValue::Bool(true).abi_encode());
+
+        assert!(matches!(
+            Parser::new(&abi).parse(&log),
+            Err(ParsingError::TopicCountMismatch {
+                expected: 1,
+                found: 2
+            })
+        ));
+
+        let lax = ParserBuilder::new().strict(false).build(&abi);
+        let event = lax.parse(&log).unwrap();
+        assert!(event.non_strict);
+        assert_eq!(event.extra_topics, [extra]);
+        assert_eq!(event.data["ok"], true);
+        assert_eq!(event.data["id"], dyn_sol_to_json(DynSolValue::from(1u64)));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["extra_topics"][0], extra.to_string());
+
+        // Missing topics can't be made up for.
+        let mut short = log.clone();
+        short.topics.truncate(1);
+        assert!(lax.parse(&short).is_err());
+    }
+
+    #[test]
+    fn truncated_data_is_padded_when_lenient() {
+        let abi = erc20_abi();
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        let mut data = B256::ZERO;
+        data[0] = 0x01;
+        let log = log(vec![transfer, B256::ZERO, B256::ZERO], data[..1].to_vec());
+
+        let strict = Parser::new(&abi);
+        assert!(matches!(
+            strict.parse(&log),
+            Err(ParsingError::DecodingFailed { .. })
+        ));
+
+        let options = OutputOptions {
+            lenient_data: true,
+            ..Default::default()
+        };
+        let lenient = Parser::with_options(&abi, options);
+        let event = lenient.parse(&log).unwrap();
+        assert!(event.padded);
+        assert_eq!(
+            event.data["value"],
+            dyn_sol_to_json(DynSolValue::Uint(
+                alloy_primitives::U256::from_be_bytes(data.0),
+                256
+            ))
+        );
+
+        let aligned = lenient.parse(&logs()[0]).unwrap();
+        assert!(!aligned.padded);
+    }
+
+    #[test]
+    fn zero_padded_topics_are_trimmed_when_lenient() {
+        let abi = erc20_abi();
+        let transfer = logs()[0].clone();
+        let mut topics: Vec<B256> = transfer.topics.iter().map(|t| B256::from(t.0)).collect();
+        topics.push(B256::ZERO);
+        let padded = log(topics.clone(), transfer.data.to_vec());
+
+        assert!(matches!(
+            Parser::new(&abi).parse(&padded),
+            Err(ParsingError::TopicCountMismatch {
+                expected: 2,
+                found: 3
+            })
+        ));
+
+        let lenient = Parser::with_options(
+            &abi,
+            OutputOptions {
+                lenient_data: true,
+                ..Default::default()
+            },
+        );
+        let event = lenient.parse(&padded).unwrap();
+        assert_eq!(event.data, lenient.parse(&transfer).unwrap().data);
+        assert!(event.extra_topics.is_empty());
+
+        // A zero `from` is a mint, not padding.
+        let mint = log(
+            vec![topics[0], B256::ZERO, topics[2]],
+            transfer.data.to_vec(),
+        );
+        let event = lenient.parse(&mint).unwrap();
+        assert_eq!(
+            event.data["from"],
+            serde_json::json!("0x0000000000000000000000000000000000000000")
+        );
+
+        // Non-zero extra topics are still rejected.
+        let mut extra = topics.clone();
+        extra[3] = B256::repeat_byte(1);
+        extra.push(B256::ZERO);
+        assert!(matches!(
+            lenient.parse(&log(extra, transfer.data.to_vec())),
+            Err(ParsingError::TopicCountMismatch {
+                expected: 2,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn non_strict_decoding_skips_validation() {
+        let abi = erc20_abi();
+        let mut log = logs().remove(0);
+        log.topics[0] = H256::repeat_byte(0x01);
+
+        let strict = Parser::new(&abi);
+        assert!(matches!(
+            strict.parse_as("Transfer", &log),
+            Err(ParsingError::DecodingFailed { .. })
+        ));
+
+        let options = OutputOptions {
+            strict: false,
+            ..Default::default()
+        };
+        let relaxed = Parser::with_options(&abi, options);
+        let decoded = relaxed.parse_as("Transfer", &log).unwrap();
+        assert!(decoded.non_strict);
+        assert!(!relaxed.parse(&logs()[0]).unwrap().non_strict);
+    }
+
+    #[test]
+    fn logs_are_parsed_lazily() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        batch[1].topics[0] = H256::zero();
+
+        let mut parsed = 0;
+        let events: Vec<_> = parser
+            .parse_iter(batch.iter().inspect(|_| parsed += 1))
+            .filter_map(Result::ok)
+            .take(2)
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(parsed, 3);
+    }
+
+    #[test]
+    fn events_are_grouped_by_name() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        batch[0].topics[0] = H256::zero();
+
+        let (grouped, skipped) = parser.parse_grouped(&batch);
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            grouped.values().map(Vec::len).sum::<usize>(),
+            batch.len() - 1
+        );
+        for (name, events) in &grouped {
+            assert!(events.iter().all(|event| &event.name == name));
+        }
+        assert!(grouped.contains_key("Transfer"));
+    }
+
+    #[test]
+    fn selectors_are_counted() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut batch = logs();
+        batch[0].topics[0] = H256::zero();
+        batch[1].topics.clear();
+
+        let (counts, unknown) = parser.count_by_selector(&batch);
+        assert_eq!(unknown, 2);
+        assert_eq!(counts.values().sum::<usize>(), batch.len() - 2);
+
+        let (grouped, _) = parser.parse_grouped(&batch[2..]);
+        for (name, events) in grouped {
+            let selector = abi.event(&name).unwrap()[0].selector();
+            assert_eq!(counts[&selector], events.len());
+        }
+    }
+
+    #[test]
+    fn parseable_logs_are_recognized() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut log = logs().remove(0);
+        assert!(parser.can_parse(&log));
+
+        log.topics[0] = H256::zero();
+        assert!(!parser.can_parse(&log));
+
+        log.topics.clear();
+        assert!(!parser.can_parse(&log));
+    }
+
+    #[test]
+    fn receipts_are_decoded_with_their_hash() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let mut receipt = ethers::core::types::TransactionReceipt {
+            transaction_hash: H256::repeat_byte(0xaa),
+            block_number: Some(42.into()),
+            ..Default::default()
+        };
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        receipt.logs = logs()
+            .into_iter()
+            .filter(|log| log.topics[0].0 == transfer.0)
+            .take(3)
+            .map(|mut log| {
+                log.transaction_hash = None;
+                log.block_number = None;
+                log
+            })
+            .collect();
+
+        let events = parser.parse_receipt(&receipt);
+        assert_eq!(events.len(), 3);
+        for event in events {
+            let event = event.unwrap();
+            assert_eq!(event.name, "Transfer");
+            let meta = event.meta.unwrap();
+            assert_eq!(meta.transaction_hash, Some(B256::repeat_byte(0xaa)));
+            assert_eq!(meta.block_number, Some(42));
+        }
+    }
+
+    #[test]
+    fn indexed_bytes32_as_hex() {
+        let abi =
+            JsonAbi::parse(["event RoleGranted(bytes32 indexed role, address indexed account)"])
+                .unwrap();
+        let options = OutputOptions {
+            fixed_bytes_encoding: Some(BytesEncoding::Hex),
+            ..Default::default()
+        };
+        let parser = Parser::with_options(&abi, options);
+        let event = abi.events().next().unwrap();
+        let role = B256::repeat_byte(0xab);
+        let log = log(
+            vec![event.selector(), role, Address::ZERO.into_word()],
+            vec![],
+        );
+        let decoded = parser.parse(&log).unwrap();
+        assert_eq!(decoded.data["role"], role.to_string());
+    }
+
+    #[test]
+    fn display_is_compact() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let from = Address::repeat_byte(0x11);
+        let to = Address::repeat_byte(0x22);
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        let log = log(
+            vec![transfer, from.into_word(), to.into_word()],
+            B256::with_last_byte(123).to_vec(),
+        );
+        assert_eq!(
+            parser.parse(&log).unwrap().to_string(),
+            format!("Transfer {{ from: {from}, to: {to}, value: 123 }}")
+        );
+    }
+
+    #[test]
+    fn parser_from_strings() {
+        let json = include_str!("../testdata/erc20.json");
+        let parser = Parser::from_json_str(json).unwrap();
+        for log in logs() {
+            parser.parse(&log).unwrap();
+        }
+        assert!(Parser::from_json_str("not json").is_err());
+
+        let human_readable = r#"[
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Approval(address indexed owner, address indexed spender, uint256 value)"
+        ]"#;
+        let compact = Parser::from_json_str(human_readable).unwrap();
+        for log in logs() {
+            assert_eq!(compact.parse(&log).unwrap(), parser.parse(&log).unwrap());
+        }
+        assert!(Parser::from_json_str(r#"["event Transfer(address"]"#).is_err());
+
+        let value: Value = serde_json::from_str(json).unwrap();
+        let parser = Parser::from_json_value(value).unwrap();
+        for log in logs() {
+            parser.parse(&log).unwrap();
+        }
+        assert!(Parser::from_json_value(Value::Bool(true)).is_err());
+
+        let parser = Parser::from_abi_items([
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ])
+        .unwrap();
+        let transfer = parser.parse(&logs()[0]).unwrap();
+        assert_eq!(transfer.name, "Transfer");
+    }
+
+    #[test]
+    fn parser_from_signatures() {
+        let parser = Parser::from_signatures(&[
+            "Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+        ])
+        .unwrap();
+        let erc20 = erc20_abi();
+        let full = Parser::new(&erc20);
+        for log in logs() {
+            assert_eq!(
+                parser.parse(&log).unwrap().data,
+                full.parse(&log).unwrap().data
+            );
+        }
+
+        assert!(matches!(
+            Parser::from_signatures(&["Transfer(address indexed from"]),
+            Err(ParsingError::InvalidSignature { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_events_pass_through() {
+        let abi = erc20_abi();
+        let parser = ParserBuilder::new().passthrough_unknown(true).build(&abi);
+        let mut log = logs().remove(0);
+        log.topics[0] = H256::repeat_byte(0xab);
+        log.data = vec![0x12, 0x34].into();
+
+        let event = parser.parse(&log).unwrap();
+        assert_eq!(event.name, "UnknownEvent");
+        let topic0 = format!("0x{}", "ab".repeat(32));
+        assert_eq!(event.data["topic0"], topic0);
+        assert_eq!(event.data["topics"][0], topic0);
+        assert_eq!(
+            event.data["topics"].as_array().unwrap().len(),
+            log.topics.len()
+        );
+        assert_eq!(event.data["data"], "0x1234");
+
+        log.topics.clear();
+        let event = parser.parse(&log).unwrap();
+        assert_eq!(event.data["topic0"], Value::Null);
+        assert_eq!(event.data["topics"], serde_json::json!([]));
+
+        assert!(Parser::new(&abi).parse(&log).is_err());
+    }
+
+    #[test]
+    fn definitions_are_counted() {
+        let abi = erc20_abi();
+        let stats = Parser::new(&abi).stats();
+        assert_eq!(
+            stats,
+            ParserStats {
+                events: 2,
+                errors: 0,
+                functions: 9,
+                anonymous_events: 0,
+            }
+        );
+
+        // The same ABI twice holds no more decodable definitions.
+        let abis = [erc20_abi(), erc20_abi()];
+        assert_eq!(Parser::from_abis(&abis).stats(), stats);
+    }
+
+    #[test]
+    fn event_selectors_are_listed() {
+        let abi = erc20_abi();
+        let selectors = Parser::new(&abi).event_selectors();
+        let transfer = abi.event("Transfer").unwrap()[0].selector();
+        let approval = abi.event("Approval").unwrap()[0].selector();
+        assert!(selectors.contains(&("Transfer".to_string(), transfer)));
+        assert!(selectors.contains(&("Approval".to_string(), approval)));
+    }
+
+    #[test]
+    fn selectors_are_looked_up_by_name() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let transfer: B256 = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .parse()
+            .unwrap();
+        assert_eq!(parser.selector_of("Transfer"), Some(transfer));
+        assert_eq!(parser.selectors_of("Transfer"), [transfer]);
+        assert_eq!(parser.selector_of("Mint"), None);
+        assert!(parser.selectors_of("Mint").is_empty());
+
+        let abi = JsonAbi::parse([
+            "event Ping(address indexed from) anonymous",
+            "event Ping(uint256 indexed id)",
+            "event Pong(uint256 indexed id) anonymous",
+        ])
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let ping = abi.event("Ping").unwrap()[1].selector();
+        assert_eq!(parser.selectors_of("Ping"), [ping]);
+        assert_eq!(parser.selector_of("Ping"), Some(ping));
+        assert_eq!(parser.selector_of("Pong"), None);
+    }
+
+    #[test]
+    fn required_events_are_checked() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        parser.require_events(&["Transfer", "Approval"]).unwrap();
+
+        let err = parser
+            .require_events(&["Transfer", "Mint", "Burn"])
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            ParsingError::MissingEvents { names } if names == &["Mint", "Burn"]
+        ));
+        assert_eq!(err.to_string(), "no events named Mint, Burn in the abi");
+    }
+
+    #[test]
+    fn keys_follow_the_configured_case() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "Sent",
+                "anonymous": false,
+                "inputs": [
+                    { "name": "packet_sequence", "type": "uint64", "indexed": true },
+                    { "name": "packet", "type": "tuple", "indexed": false, "components": [
+                        { "name": "timeout_height", "type": "uint64" },
+                        { "name": "sourcePort", "type": "string" }
+                    ] }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let event = abi.events().next().unwrap();
+        let data = DynSolValue::Tuple(vec![DynSolValue::Tuple(vec![
+            DynSolValue::Uint(alloy_primitives::U256::from(10), 64),
+            DynSolValue::String("transfer".to_string()),
+        ])])
+        .abi_encode_params();
+        let log = log(vec![event.selector(), B256::with_last_byte(7)], data);
+
+        let camel = Parser::with_options(
+            &abi,
+            OutputOptions {
+                key_case: KeyCase::CamelCase,
+                ..Default::default()
+            },
+        );
+        let event = camel.parse(&log).unwrap();
+        assert_eq!(
+            dyn_sol_to_json(DynSolValue::Uint(alloy_primitives::U256::from(7), 64)),
+            event.data["packetSequence"]
+        );
+        assert_eq!(event.data["packet"]["sourcePort"], "transfer");
+        assert!(event.data["packet"].get("timeoutHeight").is_some());
+        let (topics, data) = camel.encode(&event).unwrap();
+        assert_eq!(topics, log.topics().into_owned());
+        assert_eq!(data, log.data.to_vec());
+
+        let snake = Parser::with_options(
+            &abi,
+            OutputOptions {
+                key_case: KeyCase::SnakeCase,
+                ..Default::default()
+            },
+        );
+        let event = snake.parse(&log).unwrap();
+        assert!(event.data.get("packet_sequence").is_some());
+        assert_eq!(event.data["packet"]["source_port"], "transfer");
+        assert!(event.data["packet"].get("timeout_height").is_some());
+    }
+
+    #[test]
+    fn events_are_re_encoded() {
+        let abi = erc20_abi();
+        for split_indexed in [false, true] {
+            let options = OutputOptions {
+                split_indexed,
+                ..Default::default()
+            };
+            let parser = Parser::with_options(&abi, options);
+            for log in logs() {
+                let (topics, data) = parser.encode(&parser.parse(&log).unwrap()).unwrap();
+                assert_eq!(topics, log.topics().into_owned());
+                assert_eq!(data, log.data.to_vec());
+            }
+        }
+    }
+
+    #[test]
+    fn flattened_arrays_and_structs_are_re_encoded() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{
+                "type": "event",
+                "name": "Batch",
+                "anonymous": false,
+                "inputs": [
+                    { "name": "id", "type": "uint256", "indexed": true },
+                    { "name": "orders", "type": "tuple[]", "indexed": false, "components": [
+                        { "name": "owner", "type": "address" },
+                        { "name": "amounts", "type": "uint64[2]" }
+                    ] },
+                    { "name": "empty", "type": "uint8[]", "indexed": false },
+                    { "name": "pair", "type": "tuple", "indexed": false, "components": [
+                        { "name": "", "type": "bool" },
+                        { "name": "", "type": "bytes" }
+                    ] }
+                ]
+            }]"#,
+        )
+        .unwrap();
+        let batch = abi.events().next().unwrap();
+        let order = |owner: u8, amounts: [u64; 2]| {
+            DynSolValue::Tuple(vec![
+                DynSolValue::Address(Address::repeat_byte(owner)),
+                DynSolValue::FixedArray(amounts.map(DynSolValue::from).to_vec()),
+            ])
+        };
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Array(vec![order(1, [2, 3]), order(4, [5, 6])]),
+            DynSolValue::Array(vec![]),
+            DynSolValue::Tuple(vec![
+                DynSolValue::Bool(true),
+                DynSolValue::Bytes(vec![7, 8]),
+            ]),
+        ])
+        .abi_encode_params();
+        let log = log(vec![batch.selector(), B256::with_last_byte(9)], data);
+
+        let parser = ParserBuilder::new().flatten('/').build(&abi);
+        let event = parser.parse(&log).unwrap();
+        assert_eq!(
+            event.data["orders/1/amounts/0"],
+            dyn_sol_to_json(DynSolValue::from(5u64))
+        );
+        assert_eq!(event.data["pair/1"], "Bwg=");
+        let (topics, data) = parser.encode(&event).unwrap();
+        assert_eq!(topics, log.topics().into_owned());
+        assert_eq!(data, log.data.to_vec());
+    }
+
+    #[test]
+    fn hashed_topics_are_not_re_encoded() {
+        let abi = JsonAbi::parse(["event Named(string indexed name)"]).unwrap();
+        let parser = Parser::new(&abi);
+        let event = abi.events().next().unwrap();
+        let log = log(vec![event.selector(), B256::repeat_byte(1)], vec![]);
+        assert!(matches!(
+            parser.encode(&parser.parse(&log).unwrap()),
+            Err(ParsingError::HashedTopic { name }) if name == "name"
+        ));
+    }
+
+    #[test]
+    fn revert_string_is_decoded() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let data = alloy_primitives::hex::decode(concat!(
+            "08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "000000000000000000000000000000000000000000000000000000000000001a",
+            "4e6f7420656e6f7567682045746865722070726f76696465642e000000000000",
+        ))
+        .unwrap();
+        let decoded = parser.parse_error(&data).unwrap();
+        assert_eq!(decoded.name, "Error");
+        assert_eq!(
+            decoded.data,
+            serde_json::json!({ "reason": "Not enough Ether provided." })
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "arbitrary-precision"))]
+    fn panic_code_is_decoded() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let data = alloy_primitives::hex::decode(concat!(
+            "4e487b71",
+            "0000000000000000000000000000000000000000000000000000000000000011",
+        ))
+        .unwrap();
+        let decoded = parser.parse_error(&data).unwrap();
+        assert_eq!(decoded.name, "Panic");
+        assert_eq!(decoded.data, serde_json::json!({ "code": "17" }));
+    }
+
+    #[test]
+    fn custom_error_arguments_are_named() {
+        let abi = JsonAbi::parse([
+            "error InsufficientBalance(uint256 available, uint256 required)",
+            "error Unauthorized(address, bytes32 role)",
+        ])
+        .unwrap();
+        let parser = Parser::new(&abi);
+        let error = abi
+            .errors()
+            .find(|e| e.name == "InsufficientBalance")
+            .unwrap();
+        let available = DynSolValue::Uint(alloy_primitives::U256::from(10), 256);
+        let required = DynSolValue::Uint(alloy_primitives::U256::from(25), 256);
+        let args = DynSolValue::Tuple(vec![available.clone(), required.clone()]);
+        let data = [&error.selector()[..], &args.abi_encode_params()].concat();
+
+        let decoded = parser.parse_error(&data).unwrap();
+        assert_eq!(decoded.name, "InsufficientBalance");
+        assert_eq!(
+            decoded.data,
+            serde_json::json!({
+                "available": dyn_sol_to_json(available),
+                "required": dyn_sol_to_json(required),
+            })
+        );
+
+        // Unnamed arguments are keyed by position, as for events.
+        let error = abi.errors().find(|e| e.name == "Unauthorized").unwrap();
+        let account = DynSolValue::Address(Address::repeat_byte(0x11));
+        let role = DynSolValue::FixedBytes(B256::repeat_byte(0x22), 32);
+        let args = DynSolValue::Tuple(vec![account.clone(), role.clone()]);
+        let data = [&error.selector()[..], &args.abi_encode_params()].concat();
+        let decoded = parser.parse_error(&data).unwrap();
+        assert_eq!(
+            decoded.data,
+            serde_json::json!({
+                "arg0": dyn_sol_to_json(account),
+                "role": dyn_sol_to_json(role),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_and_short_revert_data() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        assert!(matches!(
+            parser.parse_error(&[0xde, 0xad]),
+            Err(ParsingError::ShortCalldata { len: 2 })
+        ));
+        assert!(matches!(
+            parser.parse_error(&[0xde, 0xad, 0xbe, 0xef]),
+            Err(ParsingError::UnknownError { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "arbitrary-precision"))]
+    fn transfer_calldata_is_decoded() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        let data = alloy_primitives::hex::decode(concat!(
+            "a9059cbb",
+            "0000000000000000000000008a02604a33da84f492d161c8c9fc5068f368e352",
+            "0000000000000000000000000000000000000000000000056bc75e2d63100000",
+        ))
+        .unwrap();
+        let decoded = parser.parse_call(&data).unwrap();
+        assert_eq!(decoded.name, "transfer");
+        assert_eq!(
+            decoded.data,
+            serde_json::json!({
+                "_to": "0x8a02604a33da84F492d161c8C9fc5068f368e352",
+                "_value": "100000000000000000000",
+            })
+        );
+    }
+
+    #[test]
+    fn short_and_unknown_calldata() {
+        let abi = erc20_abi();
+        let parser = Parser::new(&abi);
+        assert!(matches!(
+            parser.parse_call(&[0xa9, 0x05, 0x9c]),
+            Err(ParsingError::ShortCalldata { len: 3 })
+        ));
+        assert!(matches!(
+            parser.parse_call(&[0xde, 0xad, 0xbe, 0xef]),
+            Err(ParsingError::UnknownFunction { .. })
+        ));
+    }
+
+    mod ibc {
+        use super::*;
+
+        fn logs() -> Vec<Log> {
+            let file = include_str!("../testdata/ibc/logs.json");
+            let response: Vec<Log> = serde_json::from_str(file).unwrap();
+            response
+        }
+
+        fn abi() -> JsonAbi {
+            let json = include_str!("../testdata/ibc/abi.json");
+            serde_json::from_str(json).unwrap()
+        }
+
+        #[test]
+        fn malformed_logs_do_not_panic() {
+            let abi = abi();
+            let mut word = [0xff; 32];
+            word[..28].fill(0);
+            // One log of every event is enough, and far quicker than all.
+            let mut logs = logs();
+            logs.sort_by_key(|log| log.topics[0]);
+            logs.dedup_by_key(|log| log.topics[0]);
+            for log in logs {
+                let topics: Vec<[u8; 32]> = log.topics.iter().map(|t| t.0).collect();
+                let data = log.data.to_vec();
+                let inputs = [
+                    (topics.clone(), Vec::new()),
+                    (topics[..1].to_vec(), data.clone()),
+                    ([&topics[..], &[[0xff; 32]]].concat(), data.clone()),
+                    (topics.clone(), data[..data.len() / 2].to_vec()),
+                    (
+                        topics.clone(),
+                        data[..data.len().saturating_sub(1)].to_vec(),
+                    ),
+                    // Offsets and lengths claiming more data than there is.
+                    (topics.clone(), [word, word].concat()),
+                    (topics.clone(), data.chunks(32).flat_map(|_| word).collect()),
+                ];
+                for (topics, data) in inputs {
+                    let _ = decode_fuzz(&abi, &topics, &data);
+                }
+            }
+        }
+
+        #[test]
+        fn one_parser_for_several_contracts() {
+            let abis = [erc20_abi(), abi()];
+            let parser = Parser::from_abis(&abis);
+            for log in super::logs().iter().chain(logs().iter()) {
+                parser.parse(log).unwrap();
+            }
+        }
+
+        #[test]
+        fn json_round_trips_to_decoded_values() {
+            let abis = [erc20_abi(), abi()];
+            let parser = Parser::from_abis(&abis);
+            for log in super::logs().iter().chain(logs().iter()) {
+                let (definition, decoded) = parser.decode_log(log).unwrap();
+                let event = DecodedEvent::new(definition, decoded.event);
+                for (input, DecodedField { value, .. }) in
+                    definition.inputs.iter().zip(event.values)
+                {
+                    let ty = if input.indexed && is_hashed(input) {
+                        DynSolType::FixedBytes(32)
+                    } else {
+                        input.resolve().unwrap()
+                    };
+                    let json = dyn_sol_to_json(value.clone());
+                    assert_eq!(json_to_dyn_sol(&json, &ty).unwrap(), value);
+                }
+            }
+        }
+
+        #[test]
+        fn flattened_events_are_re_encoded() {
+            let abi = abi();
+            let mut logs = logs();
+            logs.sort_by_key(|log| log.topics[0]);
+            logs.dedup_by_key(|log| log.topics[0]);
+            let nested = Parser::new(&abi);
+            for split_indexed in [false, true] {
+                let flat = ParserBuilder::new()
+                    .flatten('.')
+                    .split_indexed(split_indexed)
+                    .build(&abi);
+                for log in &logs {
+                    let Ok(expected) = nested.encode(&nested.parse(log).unwrap()) else {
+                        continue;
+                    };
+                    let event = flat.parse(log).unwrap();
+                    assert_eq!(flat.encode(&event).unwrap(), expected, "{}", event.name);
+                }
+            }
+        }
+
+        #[test]
+        fn flattened_packets() {
+            let abi = abi();
+            let options = OutputOptions {
+                flatten: true,
+                ..Default::default()
+            };
+            let parser = Parser::with_options(&abi, options);
+            let event = logs()
+                .iter()
+                .filter_map(|log| parser.parse(log).ok())
+                .find(|event| event.name == "AcknowledgePacket")
+                .expect("testdata holds an acknowledgement");
+            let keys: Vec<&str> = event
+                .data
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(|k| k.as_str())
+                .collect();
+            assert_eq!(
+                keys,
+                [
+                    "acknowledgement",
+                    "packet.data",
+                    "packet.destination_channel",
+                    "packet.destination_port",
+                    "packet.sequence",
+                    "packet.source_channel",
+                    "packet.source_port",
+                    "packet.timeout_height.revision_height",
+                    "packet.timeout_height.revision_number",
+                    "packet.timeout_timestamp",
+                ]
+                .as_slice()
+            );
+        }
+
+        #[test]
+        fn packet_columns_follow_the_abi() {
+            let abi = abi();
+            let parser = Parser::new(&abi);
+            let flat = ParserBuilder::new().flatten('.').build(&abi);
+            let log = logs()
+                .into_iter()
+                .find(|log| {
+                    parser
+                        .parse(log)
+                        .is_ok_and(|event| event.name == "AcknowledgePacket")
+                })
+                .expect("testdata holds an acknowledgement");
+
+            let columns = parser.parse_columns(&log).unwrap();
+            let names: Vec<_> = columns.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(
+                names,
+                [
+                    "packet.sequence",
+                    "packet.source_port",
+                    "packet.source_channel",
+                    "packet.destination_port",
+                    "packet.destination_channel",
+                    "packet.data",
+                    "packet.timeout_height.revision_number",
+                    "packet.timeout_height.revision_height",
+                    "packet.timeout_timestamp",
+                    "acknowledgement",
+                ]
+            );
+            let flattened = flat.parse(&log).unwrap().data;
+            for (name, value) in &columns {
+                assert_eq!(value, &flattened[name]);
+            }
+        }
+
+        #[test]
+        fn sorted_keys_are_deterministic() {
+            let abi = abi();
+            let options = OutputOptions {
+                sorted_keys: true,
+                split_indexed: true,
+                ..Default::default()
+            };
+            let parser = Parser::with_options(&abi, options);
+            for log in logs() {
+                let first = serde_json::to_string(&parser.parse(&log).unwrap()).unwrap();
+                let second = serde_json::to_string(&parser.parse(&log).unwrap()).unwrap();
+                assert_eq!(first, second);
+
+                let event = parser.parse(&log).unwrap();
+                let keys: Vec<_> = event.data.as_object().unwrap().keys().collect();
+                assert_eq!(keys, ["body", "indexed"]);
+                if let Some(Value::Object(packet)) = event.data["body"].get("packet") {
+                    let keys: Vec<_> = packet.keys().collect();
+                    let mut sorted = keys.clone();
+                    sorted.sort();
+                    assert_eq!(keys, sorted);
+                }
+            }
+        }
+
+        #[test]
+        fn packet_schema_is_nested() {
+            let abi = abi();
+            let options = OutputOptions {
+                include_struct_type: true,
+                ..Default::default()
+            };
+            let schema = Parser::with_options(&abi, options)
+                .event_schema("AcknowledgePacket")
+                .unwrap();
+            let packet = &schema["properties"]["packet"];
+            assert_eq!(packet["properties"]["__type"]["const"], "Data");
+            assert_eq!(
+                packet["properties"]["timeout_height"]["required"],
+                serde_json::json!(["revision_height", "revision_number", "__type"])
+            );
+        }
+
+        #[test]
+        fn struct_type_names() {
+            let abi = abi();
+            let options = OutputOptions {
+                include_struct_type: true,
+                ..Default::default()
+            };
+            let parser = Parser::with_options(&abi, options);
+            let packet = logs()
+                .iter()
+                .filter_map(|log| parser.parse(log).ok())
+                .find_map(|event| event.data.get("packet").cloned())
+                .expect("testdata holds a packet event");
+            assert_eq!(packet["__type"], "Data");
+            assert!(packet.get("sequence").is_some());
+
+            let plain = Parser::new(&abi);
+            let packet = logs()
+                .iter()
+                .filter_map(|log| plain.parse(log).ok())
+                .find_map(|event| event.data.get("packet").cloned())
+                .unwrap();
+            assert!(packet.get("__type").is_none());
         }
 
         #[test]
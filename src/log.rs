@@ -0,0 +1,204 @@
+use alloc::{borrow::Cow, vec::Vec};
+use alloy_primitives::{Address, Bytes, LogData, B256};
+use serde::{Deserialize, Serialize};
+
+/// A log which can be decoded by a [`Parser`](crate::Parser).
+///
+/// Implemented for alloy's [`Log`](alloy_primitives::Log) and [`LogData`],
+/// and for `ethers::core::types::Log` when the `ethers` feature is enabled,
+/// as well as references to any of these.
+pub trait EventLog {
+    /// The topics of the log, starting with topic0 for non-anonymous events.
+    fn topics(&self) -> Cow<'_, [B256]>;
+
+    /// The ABI-encoded non-indexed parameters.
+    fn data(&self) -> &[u8];
+
+    /// The contract which emitted the log, if known.
+    fn address(&self) -> Option<Address>;
+
+    /// Provenance of the log. Defaults to only the address.
+    fn meta(&self) -> LogMeta {
+        LogMeta {
+            address: self.address(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Where and when a decoded log was emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogMeta {
+    /// The contract which emitted the log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<B256>,
+
+    /// Index of the log within its block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
+}
+
+impl<T: EventLog + ?Sized> EventLog for &T {
+    fn topics(&self) -> Cow<'_, [B256]> {
+        (**self).topics()
+    }
+
+    fn data(&self) -> &[u8] {
+        (**self).data()
+    }
+
+    fn address(&self) -> Option<Address> {
+        (**self).address()
+    }
+
+    fn meta(&self) -> LogMeta {
+        (**self).meta()
+    }
+}
+
+impl EventLog for LogData {
+    fn topics(&self) -> Cow<'_, [B256]> {
+        Cow::Borrowed(LogData::topics(self))
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn address(&self) -> Option<Address> {
+        None
+    }
+}
+
+impl EventLog for alloy_primitives::Log {
+    fn topics(&self) -> Cow<'_, [B256]> {
+        Cow::Borrowed(self.data.topics())
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data.data
+    }
+
+    fn address(&self) -> Option<Address> {
+        Some(self.address)
+    }
+}
+
+/// A log object as returned by `eth_getLogs`, with `0x`-hex topics and data.
+#[derive(Deserialize)]
+pub(crate) struct RpcLog {
+    #[serde(default)]
+    address: Option<Address>,
+    topics: Vec<B256>,
+    data: Bytes,
+}
+
+impl EventLog for RpcLog {
+    fn topics(&self) -> Cow<'_, [B256]> {
+        Cow::Borrowed(&self.topics)
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn address(&self) -> Option<Address> {
+        self.address
+    }
+}
+
+#[cfg(feature = "ethers")]
+impl EventLog for ethers::core::types::Log {
+    fn topics(&self) -> Cow<'_, [B256]> {
+        Cow::Owned(self.topics.iter().map(|t| B256::from(t.0)).collect())
+    }
+
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn address(&self) -> Option<Address> {
+        Some(Address::from(self.address.0))
+    }
+
+    fn meta(&self) -> LogMeta {
+        LogMeta {
+            address: self.address(),
+            block_number: self.block_number.map(|n| n.as_u64()),
+            transaction_hash: self.transaction_hash.map(|h| B256::from(h.0)),
+            log_index: self.log_index.and_then(|i| u64::try_from(i).ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+    use alloy_json_abi::JsonAbi;
+    use alloy_primitives::Log;
+
+    #[test]
+    fn alloy_logs_are_decoded() {
+        let abi: JsonAbi = serde_json::from_str(include_str!("../testdata/erc20.json")).unwrap();
+        let parser = Parser::new(&abi);
+        let response: serde_json::Value =
+            serde_json::from_str(include_str!("../testdata/logs.json")).unwrap();
+        let logs: Vec<Log> = serde_json::from_value(response["result"].clone()).unwrap();
+
+        for log in &logs {
+            let event = serde_json::to_value(parser.parse(log).unwrap()).unwrap();
+            let data_only = serde_json::to_value(parser.parse(&log.data).unwrap()).unwrap();
+            assert_eq!(event, data_only);
+        }
+        let meta = parser.parse_with_meta(&logs[0]).unwrap().meta.unwrap();
+        assert_eq!(meta.address, Some(logs[0].address));
+    }
+
+    #[test]
+    #[cfg(feature = "ethers")]
+    fn ethers_logs_decode_like_alloy_logs() {
+        use crate::EventLog;
+        use ethers::core::types::{Bytes, Log as EthersLog, H160, H256};
+
+        let abi: JsonAbi = serde_json::from_str(include_str!("../testdata/erc20.json")).unwrap();
+        let parser = Parser::new(&abi);
+        let response: serde_json::Value =
+            serde_json::from_str(include_str!("../testdata/logs.json")).unwrap();
+        let logs: Vec<Log> = serde_json::from_value(response["result"].clone()).unwrap();
+
+        for log in &logs {
+            let converted = EthersLog {
+                address: H160(log.address.into_array()),
+                topics: log.topics().iter().map(|t| H256(t.0)).collect(),
+                data: Bytes::from(log.data.data.to_vec()),
+                ..Default::default()
+            };
+            assert_eq!(EventLog::topics(&converted), EventLog::topics(log));
+            let expected = serde_json::to_value(parser.parse(log).unwrap()).unwrap();
+            let decoded = serde_json::to_value(parser.parse(&converted).unwrap()).unwrap();
+            assert_eq!(decoded, expected);
+        }
+    }
+
+    /// Runs on `wasm32-wasip1` with a wasm runtime configured as the cargo
+    /// runner, and only compiles on `wasm32-unknown-unknown`.
+    #[test]
+    #[cfg(target_arch = "wasm32")]
+    fn decodes_on_wasm() {
+        let abi: JsonAbi = serde_json::from_str(include_str!("../testdata/erc20.json")).unwrap();
+        let parser = Parser::new(&abi);
+        let response: serde_json::Value =
+            serde_json::from_str(include_str!("../testdata/logs.json")).unwrap();
+        let logs: Vec<Log> = serde_json::from_value(response["result"].clone()).unwrap();
+
+        // A JSON string can be handed to JavaScript and read with `JSON.parse`.
+        let json = serde_json::to_string(&parser.parse(&logs[0]).unwrap()).unwrap();
+        assert!(json.contains("\"name\":\"Transfer\""));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{EventLog, KeyedEvent, OutputOptions, Parser, ParsingError};
+use alloc::{sync::Arc, vec, vec::Vec};
+use alloy_json_abi::JsonAbi;
+use self_cell::self_cell;
+
+self_cell!(
+    struct Cell {
+        owner: Vec<JsonAbi>,
+
+        #[covariant]
+        dependent: Parser,
+    }
+);
+
+/// A [`Parser`] which owns its ABIs, so it can be stored in long-lived
+/// structs and shared across threads. Cloning is cheap: clones share the ABIs
+/// and the precomputed selector maps.
+#[derive(Clone)]
+pub struct OwnedParser {
+    cell: Arc<Cell>,
+}
+
+impl OwnedParser {
+    pub fn new(abi: JsonAbi) -> Self {
+        Self::from_abis_with_options(vec![abi], OutputOptions::default())
+    }
+
+    /// Creates a parser which converts decoded values according to `options`.
+    pub fn with_options(abi: JsonAbi, options: OutputOptions) -> Self {
+        Self::from_abis_with_options(vec![abi], options)
+    }
+
+    /// See [`Parser::from_abis`].
+    pub fn from_abis(abis: Vec<JsonAbi>) -> Self {
+        Self::from_abis_with_options(abis, OutputOptions::default())
+    }
+
+    /// See [`Parser::from_abis_with_options`].
+    pub fn from_abis_with_options(abis: Vec<JsonAbi>, options: OutputOptions) -> Self {
+        let cell = Cell::new(abis, |abis| Parser::from_abis_with_options(abis, options));
+        Self {
+            cell: Arc::new(cell),
+        }
+    }
+
+    /// The borrowed parser, giving access to every decoding method.
+    pub fn parser(&self) -> &Parser<'_> {
+        self.cell.borrow_dependent()
+    }
+
+    /// See [`Parser::parse`].
+    pub fn parse(&self, log: &impl EventLog) -> Result<KeyedEvent, ParsingError> {
+        self.parser().parse(log)
+    }
+
+    /// See [`Parser::parse_all`].
+    pub fn parse_all<L: EventLog>(&self, logs: &[L]) -> Vec<Result<KeyedEvent, ParsingError>> {
+        self.parser().parse_all(logs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Log;
+
+    fn erc20_abi() -> JsonAbi {
+        let json = include_str!("../testdata/erc20.json");
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn logs() -> Vec<Log> {
+        let file = include_str!("../testdata/logs.json");
+        let response: serde_json::Value = serde_json::from_str(file).unwrap();
+        serde_json::from_value(response["result"].clone()).unwrap()
+    }
+
+    #[test]
+    fn owned_parser_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<OwnedParser>();
+
+        let parser = OwnedParser::new(erc20_abi());
+        let logs = logs();
+        let expected = parser.parse(&logs[0]).unwrap().name;
+
+        let moved = parser.clone();
+        let decoded = std::thread::spawn(move || moved.parse(&logs[0]).unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(decoded.name, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn shared_across_tokio_tasks() {
+        let parser = Arc::new(OwnedParser::new(erc20_abi()));
+        let logs = logs();
+        let expected: Vec<_> = logs
+            .iter()
+            .map(|log| parser.parse(log).unwrap().into_value())
+            .collect();
+
+        // Each task decodes a differently rotated batch, so tasks are working
+        // on different logs at the same time.
+        let tasks: Vec<_> = (0..16)
+            .map(|offset| {
+                let parser = Arc::clone(&parser);
+                let mut batch = logs.clone();
+                batch.rotate_left(offset % logs.len());
+                tokio::spawn(async move {
+                    let decoded: Vec<_> = batch
+                        .iter()
+                        .map(|log| parser.parse(log).unwrap().into_value())
+                        .collect();
+                    (offset, decoded)
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            let (offset, mut decoded) = task.await.unwrap();
+            decoded.rotate_right(offset % logs.len());
+            assert_eq!(decoded, expected);
+        }
+    }
+}
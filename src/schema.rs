@@ -0,0 +1,209 @@
+use crate::json::param_key;
+use crate::{hashed_shape, is_hashed};
+use crate::{AddressCase, BytesEncoding, IntEncoding, OutputOptions, ParsingError};
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+use alloy_dyn_abi::{DynSolType, ResolveSolType};
+use alloy_json_abi::{Event, EventParam, Param};
+use serde_json::{json, Map, Value};
+
+/// The JSON Schema of the `data` object [`Parser::parse`] produces for
+/// `definition`.
+///
+/// [`Parser::parse`]: crate::Parser::parse
+pub(crate) fn event_schema(
+    definition: &Event,
+    opts: &OutputOptions,
+) -> Result<Value, ParsingError> {
+    if opts.etherscan_style {
+        let params = definition
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                Ok(object([
+                    (
+                        "name".to_owned(),
+                        json!({ "const": param_key(&input.name, i, opts.key_case) }),
+                    ),
+                    ("type".to_owned(), json!({ "const": input.selector_type() })),
+                    ("value".to_owned(), input_schema(input, opts)?),
+                    ("indexed".to_owned(), json!({ "const": input.indexed })),
+                ]))
+            })
+            .collect::<Result<Vec<_>, ParsingError>>()?;
+        let params = json!({
+            "type": "array",
+            "prefixItems": params,
+            "minItems": params.len(),
+            "maxItems": params.len(),
+        });
+        let mut schema = object([("params".to_owned(), params)]);
+        schema["title"] = Value::String(definition.signature());
+        return Ok(schema);
+    }
+    if opts.flatten {
+        // Flattened keys depend on the length of arrays, so only the values
+        // can be described.
+        return Ok(json!({ "title": definition.signature(), "type": "object" }));
+    }
+
+    let mut indexed = Vec::new();
+    let mut body = Vec::new();
+    for (i, input) in definition.inputs.iter().enumerate() {
+        let schema = input_schema(input, opts)?;
+        let field = (param_key(&input.name, i, opts.key_case), schema);
+        if input.indexed {
+            indexed.push(field);
+        } else {
+            body.push(field);
+        }
+    }
+
+    let mut schema = if opts.split_indexed {
+        object([
+            ("indexed".to_owned(), object(indexed)),
+            ("body".to_owned(), object(body)),
+        ])
+    } else {
+        object(indexed.into_iter().chain(body))
+    };
+    schema["title"] = Value::String(definition.signature());
+    Ok(schema)
+}
+
+fn input_schema(input: &EventParam, opts: &OutputOptions) -> Result<Value, ParsingError> {
+    let ty = input.resolve()?;
+    if input.indexed && is_hashed(input) {
+        let (key, name) = hashed_shape(input);
+        let hash = json!({ "type": "string", "pattern": "^0x[0-9a-f]{64}$" });
+        let mut properties = vec![(key.to_owned(), hash)];
+        if let Some(name) = name {
+            properties.push(("struct".to_owned(), json!({ "const": name })));
+        }
+        return Ok(object(properties));
+    }
+    if matches!(ty, DynSolType::Uint(_)) && opts.decimals.contains_key(&input.name) {
+        return Ok(json!({ "type": "string", "pattern": "^[0-9]+(\\.[0-9]+)?$" }));
+    }
+    Ok(type_schema(&ty, &input.components, opts))
+}
+
+/// Mirrors `param_to_json`: tuples whose components are all named become
+/// objects, other tuples positional arrays.
+fn type_schema(ty: &DynSolType, components: &[Param], opts: &OutputOptions) -> Value {
+    let bytes = |encoding: BytesEncoding, hex_digits: &str| match encoding {
+        BytesEncoding::Base64 => json!({ "type": "string", "contentEncoding": "base64" }),
+        BytesEncoding::Hex => json!({ "type": "string", "pattern": format!("^0x{hex_digits}$") }),
+    };
+    let sequence = |types: &[DynSolType]| -> Vec<Value> {
+        types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let components = components.get(i).map_or(&[][..], |c| &c.components);
+                type_schema(t, components, opts)
+            })
+            .collect()
+    };
+
+    match ty {
+        DynSolType::Bool => json!({ "type": "boolean" }),
+        DynSolType::Int(_) => integer_schema(true, opts),
+        DynSolType::Uint(_) => integer_schema(false, opts),
+        DynSolType::FixedBytes(size) if opts.fixed_bytes_as_array => json!({
+            "type": "array",
+            "items": byte(),
+            "minItems": size,
+            "maxItems": size,
+        }),
+        DynSolType::Bytes if opts.fixed_bytes_as_array => {
+            json!({ "type": "array", "items": byte() })
+        }
+        DynSolType::FixedBytes(_) => bytes(
+            opts.fixed_bytes_encoding.unwrap_or(opts.bytes_encoding),
+            "[0-9a-f]{64}",
+        ),
+        DynSolType::Bytes => bytes(opts.bytes_encoding, "([0-9a-f]{2})*"),
+        DynSolType::Address => {
+            let prefix = if opts.address_prefix { "0x" } else { "" };
+            let digits = match opts.address_case {
+                AddressCase::Checksummed => "[0-9a-fA-F]{40}",
+                AddressCase::Lowercase => "[0-9a-f]{40}",
+            };
+            json!({ "type": "string", "pattern": format!("^{prefix}{digits}$") })
+        }
+        DynSolType::Function => json!({ "type": "string", "pattern": "^0x[0-9a-f]{48}$" }),
+        DynSolType::String => json!({ "type": "string" }),
+        DynSolType::Array(inner) => {
+            json!({ "type": "array", "items": type_schema(inner, components, opts) })
+        }
+        DynSolType::FixedArray(inner, len) => json!({
+            "type": "array",
+            "items": type_schema(inner, components, opts),
+            "minItems": len,
+            "maxItems": len,
+        }),
+        DynSolType::Tuple(types)
+            if !components.is_empty() && components.iter().all(|c| !c.name.is_empty()) =>
+        {
+            let names = components.iter().map(|c| opts.key_case.convert(&c.name));
+            object(names.zip(sequence(types)))
+        }
+        DynSolType::Tuple(types) => json!({
+            "type": "array",
+            "prefixItems": sequence(types),
+            "minItems": types.len(),
+            "maxItems": types.len(),
+        }),
+        DynSolType::CustomStruct {
+            name,
+            prop_names,
+            tuple,
+        } => {
+            let names = prop_names.iter().map(|p| opts.key_case.convert(p));
+            let mut schema = object(names.zip(sequence(tuple)));
+            if opts.include_struct_type && !prop_names.iter().any(|p| p == "__type") {
+                schema["properties"]["__type"] = json!({ "const": name });
+                schema["required"]
+                    .as_array_mut()
+                    .expect("objects list their required keys")
+                    .push(Value::String("__type".to_owned()));
+            }
+            schema
+        }
+    }
+}
+
+fn integer_schema(signed: bool, opts: &OutputOptions) -> Value {
+    let digits = match opts.int_encoding {
+        IntEncoding::Decimal if cfg!(feature = "arbitrary-precision") => {
+            return json!({ "type": "integer" });
+        }
+        IntEncoding::Decimal if signed => "^-?[0-9]+$",
+        IntEncoding::Decimal => "^[0-9]+$",
+        IntEncoding::Hex => "^0x[0-9a-f]+$",
+    };
+    let string = json!({ "type": "string", "pattern": digits });
+    if opts.numbers_as_json_when_small {
+        json!({ "anyOf": [{ "type": "integer" }, string] })
+    } else {
+        string
+    }
+}
+
+/// An object schema requiring every one of `properties`.
+fn object(properties: impl IntoIterator<Item = (String, Value)>) -> Value {
+    let properties: Map<String, Value> = properties.into_iter().collect();
+    let required: Vec<Value> = properties.keys().cloned().map(Value::String).collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// A single byte of [`OutputOptions::fixed_bytes_as_array`].
+fn byte() -> Value {
+    json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+}